@@ -1,15 +1,15 @@
 use vut::Version;
-use vut::project::{BumpVersion, Vut};
+use vut::project::{BumpOptions, BumpVersion, Vut};
 
 use crate::error::*;
 use crate::ui::StderrUiHandler;
 
-pub fn bump(bump_version: BumpVersion) -> Result<(), CliError> {
+pub fn bump(bump_version: BumpVersion, options: &BumpOptions) -> Result<(), CliError> {
     let mut ui = StderrUiHandler::new();
 
     let mut vut = Vut::from_current_dir(&mut ui)?;
 
-    let new_version: Version = vut.bump_version(bump_version, &mut ui)?;
+    let new_version: Version = vut.bump_version(bump_version, options, &mut ui)?;
 
     eprintln!("Version bumped to {}.", new_version);
 
@@ -0,0 +1,26 @@
+use std::cmp::Ordering;
+
+use vut::{ParseMode, Version};
+
+use crate::error::*;
+
+pub fn compare(a: &str, b: &str) -> Result<Ordering, CliError> {
+    // Comparing does not write the versions anywhere, so four-part versions are always allowed
+    let parse_mode = ParseMode::STRICT.with_revision(true);
+
+    let a = Version::parse(a, parse_mode)?;
+    let b = Version::parse(b, parse_mode)?;
+
+    let ordering = a.cmp(&b);
+
+    println!(
+        "{}",
+        match ordering {
+            Ordering::Less => "less",
+            Ordering::Equal => "equal",
+            Ordering::Greater => "greater",
+        }
+    );
+
+    Ok(ordering)
+}
@@ -6,7 +6,7 @@ use crate::ui::StderrUiHandler;
 pub fn generate() -> Result<(), CliError> {
     let mut ui = StderrUiHandler::new();
 
-    let vut = Vut::from_current_dir(&mut ui)?;
+    let mut vut = Vut::from_current_dir(&mut ui)?;
 
     eprint!("Generating output... ");
 
@@ -12,7 +12,7 @@ pub fn init(example: bool, force: bool, version: Option<&str>) -> Result<(), Cli
     let current_dir = env::current_dir()?;
 
     let version: Option<Version> = match version {
-        Some(s) => Some(s.parse().map_err(|err| CliError::new(CliErrorKind::Other, err))?),
+        Some(s) => Some(s.parse()?),
         None => None,
     };
 
@@ -22,7 +22,7 @@ pub fn init(example: bool, force: bool, version: Option<&str>) -> Result<(), Cli
         config::VUT_CONFIG_DEFAULT
     };
 
-    let vut = Vut::init(current_dir, version.as_ref(), config_text, force, &mut ui)?;
+    let mut vut = Vut::init(current_dir, version.as_ref(), config_text, force, &mut ui)?;
 
     eprintln!(
         "Initialized Vut project with version {} at {}.",
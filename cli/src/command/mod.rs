@@ -1,11 +1,15 @@
 mod bump;
+mod compare;
 mod generate;
 mod get;
 mod init;
+mod satisfies;
 mod set;
 
 pub use bump::*;
+pub use compare::*;
 pub use generate::*;
 pub use get::*;
 pub use init::*;
+pub use satisfies::*;
 pub use set::*;
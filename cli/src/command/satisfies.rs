@@ -0,0 +1,26 @@
+use vut::VersionReq;
+use vut::project::Vut;
+
+use crate::error::*;
+use crate::ui::StderrUiHandler;
+
+pub fn satisfies(requirement: &str) -> Result<(), CliError> {
+    let mut ui = StderrUiHandler::new();
+
+    let requirement: VersionReq = requirement.parse()?;
+
+    let vut = Vut::from_current_dir(&mut ui)?;
+
+    let version = vut.get_version(&mut ui)?;
+
+    if !requirement.matches(&version) {
+        return Err(CliError::new(
+            CliErrorKind::NotSatisfied,
+            format!("Version {} does not satisfy {}.", version, requirement),
+        ));
+    }
+
+    eprintln!("Version {} satisfies {}.", version, requirement);
+
+    Ok(())
+}
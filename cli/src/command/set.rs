@@ -1,4 +1,3 @@
-use vut::Version;
 use vut::project::Vut;
 
 use crate::error::*;
@@ -9,7 +8,7 @@ pub fn set(version: &str) -> Result<(), CliError> {
 
     let mut vut = Vut::from_current_dir(&mut ui)?;
 
-    let new_version: Version = version.parse().map_err(|err| CliError::new(CliErrorKind::Other, err))?;
+    let new_version = vut.parse_version(version)?;
 
     vut.set_version(&new_version, &mut ui)?;
 
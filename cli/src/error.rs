@@ -3,12 +3,14 @@ use std::io;
 
 use vut::project::VutError;
 use vut::util;
+use vut::{VersionParseError, VersionReqParseError};
 
 #[derive(Debug)]
 pub enum CliErrorKind {
     Arguments,
     Config,
     NoVersionSource,
+    NotSatisfied,
     Other,
 }
 
@@ -18,6 +20,7 @@ impl CliErrorKind {
             Self::Arguments => 1,
             Self::Config => 2,
             Self::NoVersionSource => 3,
+            Self::NotSatisfied => 10,
             Self::Other => 101,
         }
     }
@@ -56,6 +59,24 @@ impl From<util::FileError> for CliError {
     }
 }
 
+impl From<VersionParseError> for CliError {
+    fn from(error: VersionParseError) -> Self {
+        CliError {
+            kind: CliErrorKind::Arguments,
+            description: Cow::Owned(format!("Invalid version: {error}")),
+        }
+    }
+}
+
+impl From<VersionReqParseError> for CliError {
+    fn from(error: VersionReqParseError) -> Self {
+        CliError {
+            kind: CliErrorKind::Arguments,
+            description: Cow::Owned(format!("Invalid version requirement: {error}")),
+        }
+    }
+}
+
 impl From<VutError> for CliError {
     fn from(error: VutError) -> Self {
         let kind = match error {
@@ -67,9 +88,13 @@ impl From<VutError> for CliError {
             VutError::WriteConfig(_) => CliErrorKind::Config,
             VutError::NoVersionSource => CliErrorKind::NoVersionSource,
             VutError::VersionNotFound => CliErrorKind::NoVersionSource,
+            VutError::VersionParse(_) => CliErrorKind::Other,
+            VutError::Bump(_) => CliErrorKind::Arguments,
             VutError::VersionFileOpen(_) => CliErrorKind::Other,
             VutError::VersionFileRead(_) => CliErrorKind::Other,
             VutError::VersionFileWrite(_) => CliErrorKind::Other,
+            VutError::FourPartVersionsDisabled => CliErrorKind::Config,
+            VutError::RevisionNotSupported(_) => CliErrorKind::Other,
             VutError::TemplateGenerate(_) => CliErrorKind::Other,
             VutError::Other(_) => CliErrorKind::Other,
         };
@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use clap::Parser;
 
 mod command;
@@ -6,7 +8,7 @@ mod ui;
 
 use tracing::debug;
 use tracing_subscriber::{EnvFilter, FmtSubscriber};
-use vut::project::BumpVersion;
+use vut::project::{BumpOptions, BumpVersion};
 
 #[derive(Debug, Parser)]
 #[clap(name = "Vut", version = env!("CARGO_PKG_VERSION"), author = env!("CARGO_PKG_AUTHORS"))]
@@ -47,8 +49,41 @@ enum Command {
 
     #[clap(name = "bump", about = "Bump version")]
     Bump {
-        #[clap(help = "Version to bump (major|minor|prerelease|build)")]
+        #[clap(help = "Version to bump (major|minor|patch|revision|prerelease|build|channel|release|calendar)")]
         bump_version: BumpVersion,
+
+        #[clap(
+            long = "pre",
+            visible_alias = "channel",
+            help = "Prerelease channel to start or switch to (e.g. beta)"
+        )]
+        pre: Option<String>,
+
+        #[clap(long = "strip-build", help = "Remove build metadata when releasing")]
+        strip_build: bool,
+    },
+
+    #[clap(
+        name = "compare",
+        about = "Compare two versions by SemVer precedence",
+        long_about = "Compare two versions by SemVer precedence.\n\nPrints 'less', 'equal' or 'greater' and exits with code 0 if the versions are equal, 11 if the first version is less than the second and 12 if it is greater."
+    )]
+    Compare {
+        #[clap(name = "a", help = "First version")]
+        a: String,
+
+        #[clap(name = "b", help = "Second version")]
+        b: String,
+    },
+
+    #[clap(
+        name = "satisfies",
+        about = "Check whether the version satisfies a requirement",
+        long_about = "Check whether the version satisfies a requirement, such as '^1.2', '>=2.0.0-0, <3' or '~1.4.1'.\n\nExits with code 10 if the requirement is not satisfied."
+    )]
+    Satisfies {
+        #[clap(name = "requirement", help = "Version requirement")]
+        requirement: String,
     },
 
     #[clap(name = "generate", alias = "gen", about = "Generate template output")]
@@ -63,8 +98,24 @@ fn main() {
 
     debug!("Debug logging enabled.");
 
+    // Exit code to return if the command succeeds
+    let mut exit_code = 0;
+
     let cmd_result = match opt.command {
-        Command::Bump { bump_version } => command::bump(bump_version),
+        Command::Bump {
+            bump_version,
+            pre,
+            strip_build,
+        } => command::bump(
+            bump_version,
+            &BumpOptions {
+                prerelease_channel: pre,
+                strip_build,
+            },
+        ),
+        Command::Compare { a, b } => command::compare(&a, &b).map(|ordering| {
+            exit_code = compare_exit_code(ordering);
+        }),
         Command::Generate => command::generate(),
         Command::Get { format } => command::get(&format),
         Command::Init {
@@ -72,11 +123,16 @@ fn main() {
             force,
             version,
         } => command::init(example, force, version.as_deref()),
+        Command::Satisfies { requirement } => command::satisfies(&requirement),
         Command::Set { version } => command::set(&version),
     };
 
     match cmd_result {
-        Ok(_) => {}
+        Ok(_) => {
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
+        }
         Err(err) => {
             // Print error description to stderr
             eprintln!("{}", err.description);
@@ -87,6 +143,14 @@ fn main() {
     };
 }
 
+fn compare_exit_code(ordering: Ordering) -> i32 {
+    match ordering {
+        Ordering::Equal => 0,
+        Ordering::Less => 11,
+        Ordering::Greater => 12,
+    }
+}
+
 fn initialize_logging() {
     let subscriber = FmtSubscriber::builder()
         .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")))
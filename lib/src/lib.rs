@@ -1,9 +1,12 @@
 mod file_updater;
 pub mod project;
+mod scheme;
 mod template;
 pub mod ui;
 pub mod util;
 mod version;
+mod version_req;
 mod version_source;
 
-pub use version::Version;
+pub use version::{BumpError, ParseMode, Version, VersionComponent, VersionParseError};
+pub use version_req::{VersionReq, VersionReqParseError};
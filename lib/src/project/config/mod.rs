@@ -7,25 +7,35 @@ use std::str::FromStr;
 use serde_derive::Deserialize;
 
 use crate::project::VutError;
+use crate::scheme::CalVerFormat;
+use crate::template::{self, TemplateInput, processor::VutProcessor};
 use crate::util;
+use crate::version::Version;
+use crate::version_source::{DEFAULT_HELM_TEMPLATE, HelmOptions, MavenOptions, NuspecOptions, VersionSourceOptions};
 
 mod custom_file_updater;
 mod custom_source_type;
 mod glob;
+mod numeric_version;
 mod regex;
 mod template_processor;
 mod templates;
 mod update_files;
 mod update_version_sources;
+mod version_parsing;
+mod version_scheme;
 
 pub use self::custom_file_updater::*;
 pub use self::custom_source_type::*;
 pub use self::glob::*;
+pub use self::numeric_version::*;
 pub use self::regex::*;
 pub use self::template_processor::*;
 pub use self::templates::*;
 pub use self::update_files::*;
 pub use self::update_version_sources::*;
+pub use self::version_parsing::*;
+pub use self::version_scheme::*;
 
 pub const VUT_CONFIG_DEFAULT: &str = include_str!("default_config.toml");
 pub const VUT_CONFIG_EXAMPLE: &str = include_str!("example_config.toml");
@@ -40,6 +50,128 @@ pub struct VersionSourceTypes(pub HashSet<String>);
 #[serde(rename_all = "kebab-case")]
 pub struct General {
     pub ignore: Option<Globs>,
+    pub version_parsing: Option<VersionParsingType>,
+    pub four_part_versions: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Bump {
+    pub prerelease_channels: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Cargo {
+    pub sync_lockfile: bool,
+}
+
+/// Whether a field is updated, or the template used to render it
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum HelmFieldDef {
+    Enabled(bool),
+    Template(String),
+}
+
+impl HelmFieldDef {
+    /// Get the template used to render the field, or None if it is not updated.
+    pub fn template(&self) -> Option<String> {
+        match self {
+            Self::Enabled(true) => Some(DEFAULT_HELM_TEMPLATE.to_owned()),
+            Self::Enabled(false) => None,
+            Self::Template(template) => Some(template.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Maven {
+    pub update_parent_version: bool,
+}
+
+impl Default for Maven {
+    fn default() -> Self {
+        Self {
+            update_parent_version: true,
+        }
+    }
+}
+
+impl Maven {
+    pub fn to_options(&self) -> MavenOptions {
+        MavenOptions {
+            update_parent_version: self.update_parent_version,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Nuspec {
+    pub chocolatey: bool,
+}
+
+impl Nuspec {
+    pub fn to_options(&self) -> NuspecOptions {
+        NuspecOptions {
+            chocolatey: self.chocolatey,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Helm {
+    pub version: HelmFieldDef,
+    pub app_version: HelmFieldDef,
+}
+
+impl Default for Helm {
+    fn default() -> Self {
+        Self {
+            version: HelmFieldDef::Enabled(true),
+            app_version: HelmFieldDef::Enabled(false),
+        }
+    }
+}
+
+impl Helm {
+    pub fn to_options(&self) -> HelmOptions {
+        HelmOptions {
+            version: self.version.template(),
+            app_version: self.app_version.template(),
+        }
+    }
+
+    /// Make sure the version can be read back from Chart.yaml.
+    /// If only the app version is updated, it is also where the version is read from,
+    /// so its template must render the version unchanged.
+    fn validate(&self) -> Result<(), VutError> {
+        let (None, Some(template)) = (self.version.template(), self.app_version.template()) else {
+            return Ok(());
+        };
+
+        let version: Version = "1.2.3-beta.1".parse().unwrap();
+        let rendered = template::render_template::<VutProcessor>(&template, &TemplateInput::from_version(&version))
+            .map_err(|err| VutError::Config(Cow::Owned(format!("Invalid Helm app-version template: {}", err))))?;
+
+        if rendered != version.to_string() {
+            return Err(VutError::Config(Cow::Owned(format!(
+                "The Helm app-version template \"{}\" must render the version unchanged, such as \"{}\", \
+                 as the version is read from appVersion when the chart version is not updated.",
+                template, DEFAULT_HELM_TEMPLATE
+            ))));
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -56,6 +188,13 @@ pub struct AuthoritativeVersionSource {
 pub struct VutConfig {
     pub general: General,
     pub authoritative_version_source: AuthoritativeVersionSource,
+    pub bump: Bump,
+    pub cargo: Cargo,
+    pub helm: Helm,
+    pub maven: Maven,
+    pub nuspec: Nuspec,
+    pub version_scheme: Option<VersionSchemeDef>,
+    pub numeric_version: Option<NumericVersionDef>,
     pub file_updaters: HashMap<String, CustomFileUpdaterTypeDef>,
     pub version_source_types: HashMap<String, CustomSourceTypeDef>,
     pub update_files: Vec<UpdateFilesDef>,
@@ -73,12 +212,38 @@ impl VutConfig {
         Self::from_str(&toml_str)
     }
 
+    /// Get the CalVer format, if a CalVer version scheme is configured.
+    pub fn calver_format(&self) -> Result<Option<CalVerFormat>, VutError> {
+        match &self.version_scheme {
+            Some(scheme) => scheme.calver_format(),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the options for built-in version sources.
+    pub fn version_source_options(&self) -> VersionSourceOptions {
+        VersionSourceOptions {
+            helm: self.helm.to_options(),
+            maven: self.maven.to_options(),
+            nuspec: self.nuspec.to_options(),
+        }
+    }
+
     pub fn legacy() -> Self {
         Self {
             general: General {
                 ignore: Some(Globs::Single("**/.git".to_owned())),
+                version_parsing: None,
+                four_part_versions: false,
             },
             authoritative_version_source: Default::default(),
+            bump: Default::default(),
+            cargo: Default::default(),
+            helm: Default::default(),
+            maven: Default::default(),
+            nuspec: Default::default(),
+            version_scheme: None,
+            numeric_version: None,
             file_updaters: HashMap::new(),
             version_source_types: HashMap::new(),
             update_files: Vec::new(),
@@ -100,6 +265,8 @@ impl FromStr for VutConfig {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let config: VutConfig = toml::from_str(s).map_err(|err| VutError::ParseConfig(Cow::Owned(err.to_string())))?;
 
+        config.helm.validate()?;
+
         Ok(config)
     }
 }
@@ -112,3 +279,17 @@ pub fn create_config_file(path: &Path, text: &str) -> Result<VutConfig, VutError
 
     VutConfig::from_str(text)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Test that an app version template that cannot be read back is rejected when the chart version is disabled
+    fn test_helm_app_version_only() {
+        assert!(VutConfig::from_str("[helm]\nversion = false\napp-version = true\n").is_ok());
+        assert!(VutConfig::from_str("[helm]\nversion = false\napp-version = \"{{Version}}\"\n").is_ok());
+        assert!(VutConfig::from_str("[helm]\napp-version = \"v{{Version}}\"\n").is_ok());
+        assert!(VutConfig::from_str("[helm]\nversion = false\napp-version = \"v{{Version}}\"\n").is_err());
+    }
+}
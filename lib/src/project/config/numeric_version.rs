@@ -0,0 +1,26 @@
+use serde_derive::Deserialize;
+
+use crate::scheme::NumericVersionFormat;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "kebab-case")]
+pub struct NumericVersionDef {
+    pub minor_digits: Option<u32>,
+    pub patch_digits: Option<u32>,
+    pub revision_digits: Option<u32>,
+    pub sortable_digits: Option<u32>,
+}
+
+impl NumericVersionDef {
+    pub fn to_format(&self) -> NumericVersionFormat {
+        let default = NumericVersionFormat::default();
+
+        NumericVersionFormat {
+            minor_digits: self.minor_digits.unwrap_or(default.minor_digits),
+            patch_digits: self.patch_digits.unwrap_or(default.patch_digits),
+            revision_digits: self.revision_digits,
+            sortable_digits: self.sortable_digits.unwrap_or(default.sortable_digits),
+        }
+    }
+}
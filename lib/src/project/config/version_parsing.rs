@@ -0,0 +1,19 @@
+use serde_derive::Deserialize;
+
+use crate::version::ParseMode;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VersionParsingType {
+    Strict,
+    Lenient,
+}
+
+impl VersionParsingType {
+    pub fn to_parse_mode(&self) -> ParseMode {
+        match self {
+            Self::Strict => ParseMode::STRICT,
+            Self::Lenient => ParseMode::LENIENT,
+        }
+    }
+}
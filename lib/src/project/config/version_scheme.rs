@@ -0,0 +1,32 @@
+use std::borrow::Cow;
+
+use serde_derive::Deserialize;
+
+use crate::project::VutError;
+use crate::scheme::CalVerFormat;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[serde(tag = "type")]
+pub enum VersionSchemeDef {
+    Semver,
+    Calver(CalVerSchemeDef),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CalVerSchemeDef {
+    pub format: String,
+}
+
+impl VersionSchemeDef {
+    /// Get the CalVer format, if this is a CalVer scheme.
+    pub fn calver_format(&self) -> Result<Option<CalVerFormat>, VutError> {
+        match self {
+            Self::Semver => Ok(None),
+            Self::Calver(def) => Ok(Some(
+                def.format.parse().map_err(|err| VutError::Config(Cow::Owned(err)))?,
+            )),
+        }
+    }
+}
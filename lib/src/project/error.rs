@@ -6,6 +6,7 @@ use thiserror::Error;
 
 use crate::template::RenderTemplateError;
 use crate::util;
+use crate::version::{BumpError, VersionParseError};
 
 #[derive(Debug, Error)]
 pub enum VutError {
@@ -15,9 +16,9 @@ pub enum VutError {
     OpenConfig(util::FileError),
     #[error("Error reading config")]
     ReadConfig(io::Error),
-    #[error("Error parsing config")]
+    #[error("Error parsing config: {0}")]
     ParseConfig(Cow<'static, str>),
-    #[error("Configuration error")]
+    #[error("Configuration error: {0}")]
     Config(Cow<'static, str>),
     #[error("Error writing config")]
     WriteConfig(io::Error),
@@ -25,14 +26,22 @@ pub enum VutError {
     NoVersionSource,
     #[error("No version found in version source")]
     VersionNotFound,
+    #[error("Invalid version: {0}")]
+    VersionParse(VersionParseError),
+    #[error("Cannot bump version: {0}")]
+    Bump(#[from] BumpError),
     #[error("Error opening version source")]
     VersionFileOpen(util::FileError),
     #[error("Error reading version source")]
     VersionFileRead(io::Error),
     #[error("Error writing to version source")]
     VersionFileWrite(io::Error),
-    #[error("Error generating template")]
+    #[error("Four-part versions are not enabled. Set four-part-versions = true in [general] to use them.")]
+    FourPartVersionsDisabled,
+    #[error("Version source at '{0}' only supports SemVer versions, which cannot have a fourth component")]
+    RevisionNotSupported(PathBuf),
+    #[error("Error generating template: {0}")]
     TemplateGenerate(RenderTemplateError),
-    #[error("Error")]
+    #[error("{0}")]
     Other(Cow<'static, str>),
 }
@@ -6,7 +6,7 @@ use tracing::debug;
 
 use crate::template::{self, TemplateInput};
 use crate::util;
-use crate::version::{self, Version};
+use crate::version::Version;
 
 use super::{VutConfig, VutError, config};
 
@@ -123,68 +123,39 @@ pub fn generate_template_output(
     Ok(())
 }
 
-pub fn generate_template_input(version: &Version) -> Result<TemplateInput, VutError> {
-    let mut template_input = TemplateInput::new();
-    let values = &mut template_input.values;
-
-    let split_prerelease = version
-        .prerelease
-        .as_ref()
-        .and_then(|p| version::split_numbered_prerelease(p));
-    let split_build = version
-        .build
-        .as_ref()
-        .and_then(|b| version::split_numbered_prerelease(b));
-
-    values.insert("FullVersion".to_owned(), version.to_string());
-    values.insert(
-        "Version".to_owned(),
-        Version {
-            build: None,
-            ..version.clone()
-        }
-        .to_string(),
-    );
-    values.insert(
-        "MajorMinorPatch".to_owned(),
-        format!("{}.{}.{}", version.major, version.minor, version.patch),
-    );
-    values.insert("MajorMinor".to_owned(), format!("{}.{}", version.major, version.minor));
-    values.insert("Major".to_owned(), format!("{}", version.major));
-    values.insert("Minor".to_owned(), format!("{}", version.minor));
-    values.insert("Patch".to_owned(), format!("{}", version.patch));
-    values.insert(
-        "Prerelease".to_owned(),
-        version.prerelease.as_ref().map_or("", |p| p).to_owned(),
-    );
-    values.insert(
-        "PrereleasePrefix".to_owned(),
-        split_prerelease
-            .map(|sp| sp.0.to_owned())
-            .unwrap_or_else(|| "".to_owned()),
-    );
-    values.insert(
-        "PrereleaseNumber".to_owned(),
-        split_prerelease
-            .map(|sp| format!("{}", sp.1))
-            .unwrap_or_else(|| "".to_owned()),
-    );
-    values.insert("Build".to_owned(), version.build.as_ref().map_or("", |b| b).to_owned());
-    values.insert(
-        "BuildPrefix".to_owned(),
-        split_build.map(|sp| sp.0.to_owned()).unwrap_or_else(|| "".to_owned()),
-    );
-    values.insert(
-        "BuildNumber".to_owned(),
-        split_build
-            .map(|sp| format!("{}", sp.1))
-            .unwrap_or_else(|| "".to_owned()),
-    );
+pub fn generate_template_input(config: &VutConfig, version: &Version) -> Result<TemplateInput, VutError> {
+    let mut template_input = TemplateInput::from_version(version);
+
+    // Numeric version variables are only available when configured, as not every version can be encoded.
+    // If the version cannot be encoded, using them in a template fails, but nothing else does.
+    if let Some(numeric_version) = &config.numeric_version {
+        let format = numeric_version.to_format();
+
+        template_input.insert_result(
+            "VersionCode",
+            format.version_code(version).map(|code| format!("{}", code)),
+        );
+        template_input.insert_result("SortableVersion", format.sortable_version(version));
+    }
+
+    // Calendar version variables are only available when using a CalVer version scheme.
+    if let Some(calver_format) = config.calver_format()? {
+        let values = &mut template_input.values;
+        let components = calver_format.components(version);
+        let component_str = |c: Option<u32>| c.map(|c| format!("{}", c)).unwrap_or_else(|| "".to_owned());
+
+        values.insert("CalendarVersion".to_owned(), calver_format.render(version));
+        values.insert("CalendarYear".to_owned(), component_str(components.year));
+        values.insert("CalendarMonth".to_owned(), component_str(components.month));
+        values.insert("CalendarWeek".to_owned(), component_str(components.week));
+        values.insert("CalendarDay".to_owned(), component_str(components.day));
+        values.insert("CalendarMicro".to_owned(), component_str(components.micro));
+    }
 
     Ok(template_input)
 }
 
-fn build_template_specs(config: &VutConfig) -> Result<Vec<TemplateSpec>, VutError> {
+fn build_template_specs(config: &VutConfig) -> Result<Vec<TemplateSpec<'_>>, VutError> {
     let mut specs: Vec<TemplateSpec> = Vec::new();
 
     for cfg_t in config.templates.iter() {
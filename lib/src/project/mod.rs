@@ -3,11 +3,11 @@ use std::env;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use strum_macros::EnumString;
+use strum_macros::{AsRefStr, EnumString};
 
 use crate::template::TemplateInput;
 use crate::util;
-use crate::version::Version;
+use crate::version::{BumpError, ParseMode, Version, VersionParseError};
 use crate::version_source::{self, VersionSource, VersionSourceType};
 
 pub mod config;
@@ -26,16 +26,36 @@ use update_version_source::*;
 
 pub const VUT_CONFIG_FILENAME: &str = "vut.toml";
 
-#[derive(Clone, Debug, EnumString)]
+#[derive(AsRefStr, Clone, Debug, EnumString)]
 #[strum(serialize_all = "lowercase")]
 pub enum BumpVersion {
     Major,
     Minor,
     Patch,
+    /// Bump the fourth numeric component, adding it if it is not present.
+    Revision,
     Prerelease,
     Build,
+    /// Promote a prerelease to the next configured prerelease channel.
+    Channel,
+    /// Finalize a prerelease by removing the prerelease.
+    Release,
+    /// Roll the date components of a calendar version forward to the current date.
+    Calendar,
 }
 
+/// Options modifying how a version is bumped.
+#[derive(Clone, Debug, Default)]
+pub struct BumpOptions {
+    /// Prerelease channel to start or switch to.
+    pub prerelease_channel: Option<String>,
+    /// Remove build metadata when releasing.
+    pub strip_build: bool,
+}
+
+/// Prerelease channels used for promotion when none are configured.
+const DEFAULT_PRERELEASE_CHANNELS: &[&str] = &["alpha", "beta", "rc"];
+
 pub struct Vut {
     root_path: PathBuf,
     config: VutConfig,
@@ -171,7 +191,7 @@ impl Vut {
                 let mut version_sources = Vec::new();
 
                 if let Ok(vst) = VersionSourceType::from_str(auth_vs_type) {
-                    if let Some(source) = vst.create_from_path(&auth_vs_path) {
+                    if let Some(source) = vst.create_from_path(&auth_vs_path, &config.version_source_options()) {
                         version_sources.push(source);
                     }
                 } else {
@@ -237,41 +257,127 @@ impl Vut {
         &self.root_path
     }
 
+    /// Get the parse mode used to read the version from the authoritative version source.
+    pub fn parse_mode(&self) -> ParseMode {
+        self.config
+            .general
+            .version_parsing
+            .as_ref()
+            .map(|vp| vp.to_parse_mode())
+            .unwrap_or(ParseMode::STRICT)
+            .with_revision(self.config.general.four_part_versions)
+    }
+
+    /// Parse a version specified by the user.
+    /// It must be strict SemVer, with an optional revision if four-part versions are enabled.
+    pub fn parse_version(&self, version_str: &str) -> Result<Version, VersionParseError> {
+        let parse_mode = ParseMode::STRICT.with_revision(self.config.general.four_part_versions);
+
+        Version::parse(version_str, parse_mode)
+    }
+
     pub fn get_version(&self, _ui: &mut dyn VutUiHandler) -> Result<Version, VutError> {
-        self.authoritative_version_source.get_version()
+        self.authoritative_version_source.get_version(self.parse_mode())
     }
 
     pub fn set_version(&mut self, version: &Version, _ui: &mut dyn VutUiHandler) -> Result<(), VutError> {
+        self.check_revision(version)?;
+
         self.authoritative_version_source.set_version(version)
     }
 
-    pub fn bump_version(&mut self, bump_version: BumpVersion, ui: &mut dyn VutUiHandler) -> Result<Version, VutError> {
-        let version = self.get_version(ui)?;
+    /// Make sure that a version with a revision is allowed before writing it.
+    fn check_revision(&self, version: &Version) -> Result<(), VutError> {
+        if version.revision.is_none() {
+            return Ok(());
+        }
 
-        let version = match bump_version {
-            BumpVersion::Major => version.bump_major(),
-            BumpVersion::Minor => version.bump_minor(),
-            BumpVersion::Patch => version.bump_patch(),
-            BumpVersion::Prerelease => version.bump_prerelease(),
-            BumpVersion::Build => version.bump_build(),
-        };
+        if !self.config.general.four_part_versions {
+            return Err(VutError::FourPartVersionsDisabled);
+        }
 
-        self.authoritative_version_source.set_version(&version)?;
+        if !self.authoritative_version_source.supports_revision() {
+            return Err(VutError::RevisionNotSupported(
+                self.authoritative_version_source.get_path().to_path_buf(),
+            ));
+        }
 
-        Ok(version)
+        // Check the version sources that will be updated as well, so that nothing is written if any of them fail
+        if !self.config.update_version_sources.is_empty() {
+            let version_sources = find_update_version_sources(&self.config, &self.root_path, &self.dir_entries()?)?;
+
+            check_revision_supported(&version_sources, version)?;
+        }
+
+        Ok(())
     }
 
-    pub fn generate_template_input(&self, ui: &mut dyn VutUiHandler) -> Result<TemplateInput, VutError> {
+    pub fn bump_version(
+        &mut self,
+        bump_version: BumpVersion,
+        options: &BumpOptions,
+        ui: &mut dyn VutUiHandler,
+    ) -> Result<Version, VutError> {
         let version = self.get_version(ui)?;
 
-        generate_template_input(&version)
+        let channels: &[String] = self.config.bump.prerelease_channels.as_deref().unwrap_or_default();
+
+        if options.strip_build && !matches!(bump_version, BumpVersion::Release) {
+            return Err(BumpError::NotApplicable {
+                option: "stripping build metadata",
+                bump: bump_version.as_ref().to_owned(),
+            }
+            .into());
+        }
+
+        let version = match (bump_version, options.prerelease_channel.as_deref()) {
+            (BumpVersion::Major, None) => version.bump_major(),
+            (BumpVersion::Minor, None) => version.bump_minor(),
+            (BumpVersion::Patch, None) => version.bump_patch(),
+            (BumpVersion::Revision, None) => version.bump_revision(),
+            (BumpVersion::Major, Some(channel)) => version.bump_major().start_prerelease(channel, channels)?,
+            (BumpVersion::Minor, Some(channel)) => version.bump_minor().start_prerelease(channel, channels)?,
+            (BumpVersion::Patch, Some(channel)) => version.bump_patch().start_prerelease(channel, channels)?,
+            (BumpVersion::Revision, Some(channel)) => version.bump_revision().start_prerelease(channel, channels)?,
+            (BumpVersion::Prerelease, None) => version.bump_prerelease()?,
+            (BumpVersion::Prerelease, Some(channel)) => version.bump_prerelease_channel(channel, channels)?,
+            (BumpVersion::Build, None) => version.bump_build()?,
+            (BumpVersion::Channel, None) => {
+                if channels.is_empty() {
+                    let default_channels: Vec<String> =
+                        DEFAULT_PRERELEASE_CHANNELS.iter().map(|c| (*c).to_owned()).collect();
+
+                    version.promote_prerelease_channel(&default_channels)?
+                } else {
+                    version.promote_prerelease_channel(channels)?
+                }
+            }
+            (BumpVersion::Release, None) => version.release(options.strip_build)?,
+            (BumpVersion::Calendar, None) => {
+                let format = self.config.calver_format()?.ok_or(BumpError::CalendarNotConfigured)?;
+
+                format.bump(&version, &util::Date::today())?
+            }
+            (bump_version, Some(_)) => {
+                return Err(BumpError::NotApplicable {
+                    option: "a prerelease channel",
+                    bump: bump_version.as_ref().to_owned(),
+                }
+                .into());
+            }
+        };
+
+        self.check_revision(&version)?;
+
+        self.authoritative_version_source.set_version(&version)?;
+
+        Ok(version)
     }
 
-    pub fn generate_output(&self, ui: &mut dyn VutUiHandler) -> Result<(), VutError> {
+    /// Get all entries below the root path, excluding ignored paths and nested Vut projects.
+    fn dir_entries(&self) -> Result<Vec<walkdir::DirEntry>, VutError> {
         let root_path = &self.root_path;
 
-        let version = self.get_version(ui)?;
-
         // Build ignore GlobSet from config
         let ignore_globset = match &self.config.general.ignore {
             Some(ignore) => ignore.build_globset()?,
@@ -305,12 +411,41 @@ impl Vut {
             .filter_map(|entry| entry.ok())
             .collect();
 
+        Ok(dir_entries)
+    }
+
+    pub fn generate_template_input(&self, ui: &mut dyn VutUiHandler) -> Result<TemplateInput, VutError> {
+        let version = self.get_version(ui)?;
+
+        generate_template_input(&self.config, &version)
+    }
+
+    pub fn generate_output(&mut self, ui: &mut dyn VutUiHandler) -> Result<(), VutError> {
+        let root_path = &self.root_path;
+
+        let version = self.get_version(ui)?;
+
+        let dir_entries = self.dir_entries()?;
+
         // Get template input
-        let template_input = generate_template_input(&version)?;
+        let template_input = generate_template_input(&self.config, &version)?;
 
         // Update version sources.
-        if !self.config.update_version_sources.is_empty() {
-            update_version_sources(&self.config, root_path, &version, &dir_entries)?;
+        let updated_paths = if self.config.update_version_sources.is_empty() {
+            vec![util::normalize_path(self.authoritative_version_source.get_path())]
+        } else {
+            update_version_sources(
+                &self.config,
+                root_path,
+                &version,
+                &dir_entries,
+                self.authoritative_version_source.as_mut(),
+            )?
+        };
+
+        // Update Cargo.lock files.
+        if self.config.cargo.sync_lockfile {
+            version_source::sync_cargo_lockfiles(root_path, &updated_paths, &version)?;
         }
 
         // Update files.
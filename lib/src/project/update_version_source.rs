@@ -1,20 +1,71 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::str::FromStr;
 
+use crate::util;
 use crate::version::Version;
-use crate::version_source::{self, VersionSource, VersionSourceType};
+use crate::version_source::{self, VersionSource, VersionSourceOptions, VersionSourceType};
 
 use super::{VutConfig, VutError, config};
 
+/// Update all version sources matched by the update-version-sources configuration,
+/// and references between them and the authoritative version source.
+/// Returns the normalized paths of all updated version sources, including the authoritative one.
 pub fn update_version_sources(
     config: &VutConfig,
     root_path: &Path,
     version: &Version,
     dir_entries: &[walkdir::DirEntry],
-) -> Result<(), VutError> {
+    authoritative_version_source: &mut dyn VersionSource,
+) -> Result<Vec<PathBuf>, VutError> {
+    let version_sources = find_update_version_sources(config, root_path, dir_entries)?;
+
+    // Make sure that all version sources can be updated before writing to any of them
+    check_revision_supported(&version_sources, version)?;
+
+    set_version_sources(version_sources, authoritative_version_source, version)
+}
+
+/// Set the version of each version source, and then update references between them
+/// and the authoritative version source, which has already been set, such as path dependencies.
+fn set_version_sources(
+    mut version_sources: Vec<Box<dyn VersionSource>>,
+    authoritative_version_source: &mut dyn VersionSource,
+    version: &Version,
+) -> Result<Vec<PathBuf>, VutError> {
+    for vs in version_sources.iter_mut() {
+        vs.set_version(version)?;
+    }
+
+    // The authoritative version source may not be matched by any of the globs,
+    // but references to it need to be updated all the same.
+    let mut updated_paths = vec![util::normalize_path(authoritative_version_source.get_path())];
+
+    for vs in version_sources.iter() {
+        let path = util::normalize_path(vs.get_path());
+
+        if !updated_paths.contains(&path) {
+            updated_paths.push(path);
+        }
+    }
+
+    authoritative_version_source.update_dependencies(&updated_paths, version)?;
+
+    for vs in version_sources.iter_mut() {
+        vs.update_dependencies(&updated_paths, version)?;
+    }
+
+    Ok(updated_paths)
+}
+
+/// Find all version sources matched by the update-version-sources configuration.
+pub fn find_update_version_sources(
+    config: &VutConfig,
+    root_path: &Path,
+    dir_entries: &[walkdir::DirEntry],
+) -> Result<Vec<Box<dyn VersionSource>>, VutError> {
     let version_source_finder = VersionSourceFinder::from_config(config)?;
 
     let mut version_sources: Vec<Box<dyn VersionSource>> = Vec::new();
@@ -33,8 +84,15 @@ pub fn update_version_sources(
         version_sources.append(&mut version_source_finder.find_version_sources(path, rel_path));
     }
 
-    for mut vs in version_sources {
-        vs.set_version(version)?;
+    Ok(version_sources)
+}
+
+/// Return an error if the version has a revision, and any of the version sources cannot store it.
+pub fn check_revision_supported(version_sources: &[Box<dyn VersionSource>], version: &Version) -> Result<(), VutError> {
+    if version.revision.is_some()
+        && let Some(vs) = version_sources.iter().find(|vs| !vs.supports_revision())
+    {
+        return Err(VutError::RevisionNotSupported(vs.get_path().to_path_buf()));
     }
 
     Ok(())
@@ -46,9 +104,13 @@ enum VersionSourceTemplate {
 }
 
 impl VersionSourceTemplate {
-    pub fn version_source_from_path(&self, path: &Path) -> Option<Box<dyn VersionSource>> {
+    pub fn version_source_from_path(
+        &self,
+        path: &Path,
+        options: &VersionSourceOptions,
+    ) -> Option<Box<dyn VersionSource>> {
         match self {
-            VersionSourceTemplate::Builtin(vst) => vst.create_from_path(path),
+            VersionSourceTemplate::Builtin(vst) => vst.create_from_path(path, options),
             VersionSourceTemplate::CustomRegex(template) => template
                 .instance_from_path(path)
                 .map(|vs| Box::new(vs) as Box<dyn VersionSource>),
@@ -101,10 +163,10 @@ impl CustomSourceTypes {
     }
 
     pub fn version_source_from_path(&self, path: &Path, source_type: &str) -> Option<Box<dyn VersionSource>> {
-        if let Some(custom_source_type_template) = self.regex_source_types.get(source_type) {
-            if let Some(source) = custom_source_type_template.instance_from_path(path) {
-                return Some(Box::new(source));
-            }
+        if let Some(custom_source_type_template) = self.regex_source_types.get(source_type)
+            && let Some(source) = custom_source_type_template.instance_from_path(path)
+        {
+            return Some(Box::new(source));
         }
 
         None
@@ -115,12 +177,14 @@ struct VersionSourceSpec {
     include_globset: globset::GlobSet,
     exclude_globset: Option<globset::GlobSet>,
     source_templates: Option<Vec<VersionSourceTemplate>>,
+    options: VersionSourceOptions,
 }
 
 impl VersionSourceSpec {
     pub fn from_config_vs(
         def: &config::UpdateVersionSourcesDef,
         custom_source_types: &CustomSourceTypes,
+        options: &VersionSourceOptions,
     ) -> Result<Self, VutError> {
         let include_globset = def.globs.build_globset()?;
         let exclude_globset = match &def.exclude_globs {
@@ -157,6 +221,7 @@ impl VersionSourceSpec {
             include_globset,
             exclude_globset,
             source_templates,
+            options: options.clone(),
         })
     }
 
@@ -164,11 +229,11 @@ impl VersionSourceSpec {
         let mut version_sources = Vec::new();
 
         if self.include_globset.is_match(rel_path) {
-            if let Some(exclude_globset) = &self.exclude_globset {
-                if exclude_globset.is_match(rel_path) {
-                    // If path is matched by the exclude globset, immediately return the empty list.
-                    return version_sources;
-                }
+            if let Some(exclude_globset) = &self.exclude_globset
+                && exclude_globset.is_match(rel_path)
+            {
+                // If path is matched by the exclude globset, immediately return the empty list.
+                return version_sources;
             }
 
             // Check for built-in version sources at this path
@@ -177,10 +242,10 @@ impl VersionSourceSpec {
                     // Find built-in sources
                     source_templates
                         .iter()
-                        .filter_map(|st| st.version_source_from_path(path))
+                        .filter_map(|st| st.version_source_from_path(path, &self.options))
                         .collect()
                 }
-                _ => version_source::version_sources_from_path(path),
+                _ => version_source::version_sources_from_path(path, &self.options),
             };
 
             // Append all found sources to the main list of sources
@@ -198,11 +263,12 @@ pub struct VersionSourceFinder {
 impl VersionSourceFinder {
     pub fn from_config(config: &VutConfig) -> Result<Self, VutError> {
         let custom_source_types = CustomSourceTypes::from_config(config)?;
+        let options = config.version_source_options();
 
         let mut specs: Vec<VersionSourceSpec> = Vec::new();
 
         for cfg_vs in config.update_version_sources.iter() {
-            let spec = VersionSourceSpec::from_config_vs(cfg_vs, &custom_source_types)?;
+            let spec = VersionSourceSpec::from_config_vs(cfg_vs, &custom_source_types, &options)?;
 
             specs.push(spec);
         }
@@ -217,3 +283,68 @@ impl VersionSourceFinder {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::version::ParseMode;
+
+    /// Version source that records the updated paths it is given.
+    struct RecordingSource {
+        path: PathBuf,
+        updated_paths: Rc<RefCell<Vec<PathBuf>>>,
+    }
+
+    impl RecordingSource {
+        fn new(path: &str) -> (Self, Rc<RefCell<Vec<PathBuf>>>) {
+            let updated_paths = Rc::new(RefCell::new(Vec::new()));
+
+            let source = Self {
+                path: PathBuf::from(path),
+                updated_paths: updated_paths.clone(),
+            };
+
+            (source, updated_paths)
+        }
+    }
+
+    impl VersionSource for RecordingSource {
+        fn get_path(&self) -> &Path {
+            &self.path
+        }
+
+        fn exists(&self) -> bool {
+            true
+        }
+
+        fn get_version(&self, _parse_mode: ParseMode) -> Result<Version, VutError> {
+            Err(VutError::VersionNotFound)
+        }
+
+        fn set_version(&mut self, _version: &Version) -> Result<(), VutError> {
+            Ok(())
+        }
+
+        fn update_dependencies(&mut self, updated_paths: &[PathBuf], _version: &Version) -> Result<(), VutError> {
+            *self.updated_paths.borrow_mut() = updated_paths.to_vec();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    /// Test that references to and from an authoritative version source outside the globs are updated
+    fn test_set_version_sources_includes_authoritative() {
+        let (mut core, core_updated_paths) = RecordingSource::new("/repo/core");
+        let (app, app_updated_paths) = RecordingSource::new("/repo/app");
+
+        let updated_paths = set_version_sources(vec![Box::new(app)], &mut core, &"0.2.0".parse().unwrap()).unwrap();
+
+        let expected = vec![PathBuf::from("/repo/core"), PathBuf::from("/repo/app")];
+        assert_eq!(updated_paths, expected);
+        assert_eq!(*core_updated_paths.borrow(), expected);
+        assert_eq!(*app_updated_paths.borrow(), expected);
+    }
+}
@@ -0,0 +1,355 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use crate::util::Date;
+use crate::version::{BumpError, Version};
+
+/// A single dot-separated segment of a CalVer format string.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CalVerSegment {
+    /// Full year (YYYY), e.g. 2006.
+    FullYear,
+    /// Short year (YY), e.g. 6.
+    ShortYear,
+    /// Zero-padded short year (0Y), e.g. 06.
+    PaddedYear,
+    /// Month (MM), e.g. 5.
+    Month,
+    /// Zero-padded month (0M), e.g. 05.
+    PaddedMonth,
+    /// ISO week (WW), e.g. 9.
+    Week,
+    /// Zero-padded ISO week (0W), e.g. 09.
+    PaddedWeek,
+    /// Day of month (DD), e.g. 7.
+    Day,
+    /// Zero-padded day of month (0D), e.g. 07.
+    PaddedDay,
+    /// Counter that is incremented for releases within the same period.
+    Micro,
+}
+
+impl CalVerSegment {
+    fn is_year(&self) -> bool {
+        matches!(self, Self::FullYear | Self::ShortYear | Self::PaddedYear)
+    }
+
+    fn is_week(&self) -> bool {
+        matches!(self, Self::Week | Self::PaddedWeek)
+    }
+
+    fn is_padded(&self) -> bool {
+        matches!(
+            self,
+            Self::PaddedYear | Self::PaddedMonth | Self::PaddedWeek | Self::PaddedDay
+        )
+    }
+}
+
+impl FromStr for CalVerSegment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "YYYY" => Self::FullYear,
+            "YY" => Self::ShortYear,
+            "0Y" => Self::PaddedYear,
+            "MM" => Self::Month,
+            "0M" => Self::PaddedMonth,
+            "WW" => Self::Week,
+            "0W" => Self::PaddedWeek,
+            "DD" => Self::Day,
+            "0D" => Self::PaddedDay,
+            "MICRO" => Self::Micro,
+            _ => return Err(format!("Invalid CalVer format segment '{s}'")),
+        })
+    }
+}
+
+/// Date components of a calendar version.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CalVerComponents {
+    pub year: Option<u32>,
+    pub month: Option<u32>,
+    pub week: Option<u32>,
+    pub day: Option<u32>,
+    pub micro: Option<u32>,
+}
+
+/// A CalVer format, such as "YYYY.MM.MICRO" or "YY.0M.DD", mapping each segment
+/// to the major, minor and patch components of a version respectively.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CalVerFormat {
+    segments: [CalVerSegment; 3],
+}
+
+impl FromStr for CalVerFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments = s
+            .split('.')
+            .map(CalVerSegment::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let segments: [CalVerSegment; 3] = segments
+            .try_into()
+            .map_err(|_| format!("CalVer format '{s}' must have exactly three segments"))?;
+
+        if !segments[0].is_year() {
+            return Err(format!("CalVer format '{s}' must start with a year"));
+        }
+
+        // The year must be followed by either a month and a day or MICRO, or by a week and MICRO.
+        let is_valid = match (segments[1], segments[2]) {
+            (CalVerSegment::Month | CalVerSegment::PaddedMonth, third) => {
+                matches!(
+                    third,
+                    CalVerSegment::Day | CalVerSegment::PaddedDay | CalVerSegment::Micro
+                )
+            }
+            (CalVerSegment::Week | CalVerSegment::PaddedWeek, third) => third == CalVerSegment::Micro,
+            _ => false,
+        };
+
+        if !is_valid {
+            return Err(format!(
+                "CalVer format '{s}' must be a year followed by a month and a day or MICRO, or by a week and MICRO"
+            ));
+        }
+
+        Ok(Self { segments })
+    }
+}
+
+impl CalVerFormat {
+    fn uses_week(&self) -> bool {
+        self.segments.iter().any(|s| s.is_week())
+    }
+
+    /// Get the value of each date segment for the specified date, or None for MICRO.
+    fn date_values(&self, date: &Date) -> [Option<u32>; 3] {
+        // When using week numbers, the ISO week-numbering year must be used for the year.
+        let (year, week) = date.iso_week();
+        let year = if self.uses_week() { year } else { date.year };
+
+        self.segments.map(|segment| match segment {
+            CalVerSegment::FullYear => Some(year.max(0) as u32),
+            CalVerSegment::ShortYear | CalVerSegment::PaddedYear => Some((year - 2000).max(0) as u32),
+            CalVerSegment::Month | CalVerSegment::PaddedMonth => Some(date.month),
+            CalVerSegment::Week | CalVerSegment::PaddedWeek => Some(week),
+            CalVerSegment::Day | CalVerSegment::PaddedDay => Some(date.day),
+            CalVerSegment::Micro => None,
+        })
+    }
+
+    /// Roll the date segments of a version forward to the specified date.
+    /// If the date segments are already up to date, the MICRO segment is incremented instead,
+    /// unless the version is a prerelease, in which case it is released without changing MICRO.
+    /// When the date segments change, MICRO is reset to 0.
+    pub fn bump(&self, version: &Version, today: &Date) -> Result<Version, BumpError> {
+        let current = [version.major, version.minor, version.patch];
+        let date_values = self.date_values(today);
+
+        let ordering = current
+            .iter()
+            .zip(date_values.iter())
+            .filter_map(|(c, d)| d.map(|d| c.cmp(&d)))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal);
+
+        let new = match ordering {
+            Ordering::Less => date_values.map(|d| d.unwrap_or(0)),
+            // A prerelease for the current date is finalized, the same as a release bump
+            Ordering::Equal if version.prerelease.is_some() => {
+                return Ok(Version {
+                    prerelease: None,
+                    ..version.clone()
+                });
+            }
+            Ordering::Equal if self.segments[2] == CalVerSegment::Micro => [current[0], current[1], current[2] + 1],
+            Ordering::Equal => return Err(BumpError::CalendarUpToDate),
+            Ordering::Greater => return Err(BumpError::CalendarInFuture),
+        };
+
+        Ok(Version {
+            major: new[0],
+            minor: new[1],
+            patch: new[2],
+            revision: version.revision.map(|_| 0),
+            prerelease: None,
+            build: version.build.clone(),
+        })
+    }
+
+    /// Render a version according to the format, including zero-padding.
+    /// Leading zeros are not allowed in SemVer, so the result is only meant for display,
+    /// and only parses back as a version in lenient mode.
+    pub fn render(&self, version: &Version) -> String {
+        let values = [version.major, version.minor, version.patch];
+
+        let core: Vec<String> = self
+            .segments
+            .iter()
+            .zip(values.iter())
+            .map(|(segment, value)| {
+                if segment.is_padded() {
+                    format!("{:02}", value)
+                } else {
+                    format!("{}", value)
+                }
+            })
+            .collect();
+
+        format!(
+            "{}{}{}",
+            core.join("."),
+            version
+                .prerelease
+                .as_ref()
+                .map_or_else(String::new, |p| format!("-{}", p)),
+            version.build.as_ref().map_or_else(String::new, |b| format!("+{}", b))
+        )
+    }
+
+    /// Extract the date components of a version according to the format.
+    pub fn components(&self, version: &Version) -> CalVerComponents {
+        let values = [version.major, version.minor, version.patch];
+        let mut components = CalVerComponents::default();
+
+        for (segment, value) in self.segments.iter().zip(values.iter().copied()) {
+            match segment {
+                CalVerSegment::FullYear => components.year = Some(value),
+                CalVerSegment::ShortYear | CalVerSegment::PaddedYear => components.year = Some(value + 2000),
+                CalVerSegment::Month | CalVerSegment::PaddedMonth => components.month = Some(value),
+                CalVerSegment::Week | CalVerSegment::PaddedWeek => components.week = Some(value),
+                CalVerSegment::Day | CalVerSegment::PaddedDay => components.day = Some(value),
+                CalVerSegment::Micro => components.micro = Some(value),
+            }
+        }
+
+        components
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::version::ParseMode;
+
+    #[test]
+    /// Test parsing of valid and invalid format strings
+    fn test_parse_format() {
+        assert!("YYYY.MM.MICRO".parse::<CalVerFormat>().is_ok());
+        assert!("YY.0M.DD".parse::<CalVerFormat>().is_ok());
+        assert!("YYYY.0W.MICRO".parse::<CalVerFormat>().is_ok());
+
+        assert!("YYYY.MM".parse::<CalVerFormat>().is_err());
+        assert!("MM.YYYY.MICRO".parse::<CalVerFormat>().is_err());
+        assert!("YYYY.MICRO.DD".parse::<CalVerFormat>().is_err());
+        assert!("YYYY.DD.MM".parse::<CalVerFormat>().is_err());
+        assert!("YYYY.WW.DD".parse::<CalVerFormat>().is_err());
+        assert!("YYYY.MM.XX".parse::<CalVerFormat>().is_err());
+    }
+
+    #[test]
+    /// Test rolling calendar versions forward
+    fn test_bump() {
+        let format: CalVerFormat = "YYYY.MM.MICRO".parse().unwrap();
+        let today = Date::new(2024, 5, 17);
+
+        assert_eq!(
+            format
+                .bump(&Version::new(2024, 4, 3, None, None), &today)
+                .unwrap()
+                .to_string(),
+            "2024.5.0"
+        );
+        assert_eq!(
+            format
+                .bump(&Version::new(2024, 5, 3, None, None), &today)
+                .unwrap()
+                .to_string(),
+            "2024.5.4"
+        );
+        assert_eq!(
+            format.bump(&Version::new(2024, 6, 0, None, None), &today).unwrap_err(),
+            BumpError::CalendarInFuture
+        );
+
+        // A prerelease for the current date is released rather than skipped
+        assert_eq!(
+            format
+                .bump(&Version::new(2024, 5, 3, Some("rc.1"), None), &today)
+                .unwrap()
+                .to_string(),
+            "2024.5.3"
+        );
+        assert_eq!(
+            format
+                .bump(&Version::new(2024, 4, 3, Some("rc.1"), None), &today)
+                .unwrap()
+                .to_string(),
+            "2024.5.0"
+        );
+
+        let format: CalVerFormat = "YY.0M.DD".parse().unwrap();
+
+        assert_eq!(
+            format
+                .bump(&Version::new(24, 5, 17, Some("beta.2"), None), &today)
+                .unwrap()
+                .to_string(),
+            "24.5.17"
+        );
+        assert_eq!(
+            format
+                .bump(&Version::new(24, 5, 1, None, None), &today)
+                .unwrap()
+                .to_string(),
+            "24.5.17"
+        );
+        assert_eq!(
+            format.bump(&Version::new(24, 5, 17, None, None), &today).unwrap_err(),
+            BumpError::CalendarUpToDate
+        );
+
+        // Week-based formats use the ISO week-numbering year
+        let format: CalVerFormat = "YYYY.WW.MICRO".parse().unwrap();
+
+        assert_eq!(
+            format
+                .bump(&Version::new(2024, 52, 2, None, None), &Date::new(2024, 12, 30))
+                .unwrap()
+                .to_string(),
+            "2025.1.0"
+        );
+    }
+
+    #[test]
+    /// Test rendering with zero-padding and extracting date components
+    fn test_render() {
+        let format: CalVerFormat = "YY.0M.0D".parse().unwrap();
+        let version = Version::new(24, 5, 7, Some("rc.1"), Some("build.1"));
+
+        assert_eq!(format.render(&version), "24.05.07-rc.1+build.1");
+        assert_eq!(version.to_string(), "24.5.7-rc.1+build.1");
+
+        // Padded versions are not valid SemVer, and are only accepted when parsing leniently
+        assert!(Version::parse("24.05.07", ParseMode::STRICT).is_err());
+        assert_eq!(
+            Version::parse("24.05.07", ParseMode::LENIENT).unwrap(),
+            Version::new(24, 5, 7, None, None)
+        );
+        assert_eq!(
+            format.components(&version),
+            CalVerComponents {
+                year: Some(2024),
+                month: Some(5),
+                week: None,
+                day: Some(7),
+                micro: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Renderings of a version in the formats expected by various packaging ecosystems.
+
+use crate::version::Version;
+
+/// Maximum length of a Docker image tag.
+const DOCKER_TAG_MAX_LENGTH: usize = 128;
+
+/// Get the numeric part of a version, including the revision if present.
+pub(crate) fn numeric_version(version: &Version) -> String {
+    match version.revision {
+        Some(revision) => format!("{}.{}.{}.{}", version.major, version.minor, version.patch, revision),
+        None => format!("{}.{}.{}", version.major, version.minor, version.patch),
+    }
+}
+
+/// Render a Debian upstream version, e.g. `1.2.3~beta.1+build.5`.
+///
+/// The prerelease is separated with a tilde, so that it sorts before the release.
+/// Hyphens are replaced, as they would otherwise be interpreted as the start of the Debian revision.
+pub fn debian_version(version: &Version) -> String {
+    let mut s = numeric_version(version);
+
+    if let Some(prerelease) = &version.prerelease {
+        s.push('~');
+        s.push_str(&prerelease.replace('-', "."));
+    }
+
+    if let Some(build) = &version.build {
+        s.push('+');
+        s.push_str(&build.replace('-', "."));
+    }
+
+    s
+}
+
+/// Render the RPM `Version` tag, e.g. `1.2.3`.
+pub fn rpm_version(version: &Version) -> String {
+    numeric_version(version)
+}
+
+/// Render the RPM `Release` tag, e.g. `0.beta.1` for prereleases and `1` for releases.
+///
+/// Prereleases get a release number starting with 0, so that they sort before the final release.
+/// Build metadata is appended, as RPM versions have no equivalent.
+pub fn rpm_release(version: &Version) -> String {
+    let mut s = match &version.prerelease {
+        Some(prerelease) => format!("0.{}", prerelease.replace('-', ".")),
+        None => "1".to_owned(),
+    };
+
+    if let Some(build) = &version.build {
+        s.push('.');
+        s.push_str(&build.replace('-', "."));
+    }
+
+    s
+}
+
+/// Render a normalized NuGet version, e.g. `1.2.3-beta.1`.
+///
+/// As in NuGet's own normalization, a zero revision is omitted and build metadata is removed.
+pub fn nuget_version(version: &Version) -> String {
+    let mut s = format!("{}.{}.{}", version.major, version.minor, version.patch);
+
+    if let Some(revision) = version.revision.filter(|r| *r != 0) {
+        s.push_str(&format!(".{}", revision));
+    }
+
+    if let Some(prerelease) = &version.prerelease {
+        s.push('-');
+        s.push_str(prerelease);
+    }
+
+    s
+}
+
+/// Render a version for Chocolatey v1, e.g. `1.2.3-beta1`.
+///
+/// Chocolatey v1 only supports SemVer 1.0 prereleases, which cannot contain dots, so they are removed.
+/// Otherwise, this is the same as the normalized NuGet version.
+pub fn chocolatey_version(version: &Version) -> String {
+    nuget_version(&Version {
+        prerelease: version.prerelease.as_ref().map(|p| p.replace('.', "")),
+        ..version.clone()
+    })
+}
+
+/// Get the four numeric components of a version, ensuring that each one fits in a u16.
+pub fn windows_version_components(version: &Version) -> Result<[u16; 4], String> {
+    let components = [
+        version.major,
+        version.minor,
+        version.patch,
+        version.revision.unwrap_or(0),
+    ];
+
+    let mut result = [0u16; 4];
+
+    for (r, c) in result.iter_mut().zip(components.iter()) {
+        *r = u16::try_from(*c).map_err(|_| format!("version component {} does not fit in 16 bits", c))?;
+    }
+
+    Ok(result)
+}
+
+/// Render a version that is valid as a Docker image tag, e.g. `1.2.3-beta.1_build.5`.
+///
+/// Docker tags cannot contain `+`, so build metadata is separated with an underscore instead.
+pub fn docker_tag(version: &Version) -> String {
+    let mut s = version.to_string().replace('+', "_");
+
+    s.truncate(DOCKER_TAG_MAX_LENGTH);
+
+    s
+}
+
+/// Render a Maven version, e.g. `1.2.3-beta.1`.
+/// Maven has no concept of build metadata, so it is removed.
+pub fn maven_version(version: &Version) -> String {
+    let mut s = numeric_version(version);
+
+    if let Some(prerelease) = &version.prerelease {
+        s.push('-');
+        s.push_str(prerelease);
+    }
+
+    s
+}
+
+/// Render a Maven snapshot version, e.g. `1.2.3-SNAPSHOT` for any prerelease of 1.2.3.
+/// Releases are rendered the same as [maven_version].
+pub fn maven_snapshot_version(version: &Version) -> String {
+    if version.prerelease.is_some() {
+        format!("{}-SNAPSHOT", numeric_version(version))
+    } else {
+        maven_version(version)
+    }
+}
+
+/// Render a RubyGems version, e.g. `1.2.3.beta.1`.
+///
+/// RubyGems treats any letter as the start of a prerelease, and converts hyphens to `.pre.`,
+/// so the prerelease is separated with a dot instead. Build metadata is not supported, and is removed.
+pub fn gem_version(version: &Version) -> String {
+    let mut s = numeric_version(version);
+
+    if let Some(prerelease) = &version.prerelease {
+        s.push('.');
+        s.push_str(&prerelease.replace('-', "."));
+    }
+
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::version::ParseMode;
+
+    #[test]
+    /// Test ecosystem renderings of a release version
+    fn test_release() {
+        let version: Version = "1.2.3".parse().unwrap();
+
+        assert_eq!(debian_version(&version), "1.2.3");
+        assert_eq!(rpm_version(&version), "1.2.3");
+        assert_eq!(rpm_release(&version), "1");
+        assert_eq!(nuget_version(&version), "1.2.3");
+        assert_eq!(chocolatey_version(&version), "1.2.3");
+        assert_eq!(docker_tag(&version), "1.2.3");
+        assert_eq!(maven_version(&version), "1.2.3");
+        assert_eq!(maven_snapshot_version(&version), "1.2.3");
+        assert_eq!(gem_version(&version), "1.2.3");
+    }
+
+    #[test]
+    /// Test ecosystem renderings of a prerelease version with build metadata
+    fn test_prerelease() {
+        let version: Version = "1.2.3-beta-x.1+build.5".parse().unwrap();
+
+        assert_eq!(debian_version(&version), "1.2.3~beta.x.1+build.5");
+        assert_eq!(rpm_version(&version), "1.2.3");
+        assert_eq!(rpm_release(&version), "0.beta.x.1.build.5");
+        assert_eq!(nuget_version(&version), "1.2.3-beta-x.1");
+        assert_eq!(chocolatey_version(&version), "1.2.3-beta-x1");
+        assert_eq!(docker_tag(&version), "1.2.3-beta-x.1_build.5");
+        assert_eq!(maven_version(&version), "1.2.3-beta-x.1");
+        assert_eq!(maven_snapshot_version(&version), "1.2.3-SNAPSHOT");
+        assert_eq!(gem_version(&version), "1.2.3.beta.x.1");
+    }
+
+    #[test]
+    /// Test that NuGet versions omit a zero revision
+    fn test_nuget_revision() {
+        let parse_mode = ParseMode::STRICT.with_revision(true);
+
+        assert_eq!(nuget_version(&Version::parse("1.2.3.0", parse_mode).unwrap()), "1.2.3");
+        assert_eq!(
+            nuget_version(&Version::parse("1.2.3.4-rc.1", parse_mode).unwrap()),
+            "1.2.3.4-rc.1"
+        );
+    }
+}
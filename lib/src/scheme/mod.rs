@@ -0,0 +1,8 @@
+mod calver;
+pub mod ecosystem;
+mod numeric;
+mod pep440;
+
+pub use calver::*;
+pub use numeric::*;
+pub use pep440::*;
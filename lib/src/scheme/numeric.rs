@@ -0,0 +1,151 @@
+use crate::version::Version;
+
+/// Largest allowed version code, as version codes are commonly stored in a signed 32-bit integer
+/// (e.g. Android's versionCode).
+pub const MAX_VERSION_CODE: u64 = i32::MAX as u64;
+
+/// Format for numeric encodings of a version.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NumericVersionFormat {
+    /// Number of decimal digits reserved for the minor version in the version code.
+    pub minor_digits: u32,
+    /// Number of decimal digits reserved for the patch version in the version code.
+    pub patch_digits: u32,
+    /// Number of decimal digits reserved for the revision in the version code.
+    /// If not specified, the revision is not included in the version code.
+    pub revision_digits: Option<u32>,
+    /// Number of digits each component is zero-padded to in the sortable version.
+    pub sortable_digits: u32,
+}
+
+impl Default for NumericVersionFormat {
+    fn default() -> Self {
+        Self {
+            minor_digits: 2,
+            patch_digits: 2,
+            revision_digits: None,
+            sortable_digits: 3,
+        }
+    }
+}
+
+impl NumericVersionFormat {
+    /// Encode a version as a single integer, e.g. `major * 10000 + minor * 100 + patch`.
+    /// Prereleases cannot be encoded, as they would get the same version code as the release,
+    /// while version codes must strictly increase.
+    pub fn version_code(&self, version: &Version) -> Result<u64, String> {
+        if let Some(prerelease) = &version.prerelease {
+            return Err(format!(
+                "prerelease {} cannot be encoded, as it would get the same version code as the release",
+                prerelease
+            ));
+        }
+
+        let mut code = u64::from(version.major);
+
+        let mut components = vec![
+            (version.minor, self.minor_digits, "minor version"),
+            (version.patch, self.patch_digits, "patch version"),
+        ];
+
+        if let Some(revision_digits) = self.revision_digits {
+            components.push((version.revision.unwrap_or(0), revision_digits, "revision"));
+        }
+
+        for (value, digits, name) in components {
+            let limit = 10u64
+                .checked_pow(digits)
+                .ok_or_else(|| format!("{} digits for {} is too many", digits, name))?;
+
+            if u64::from(value) >= limit {
+                return Err(format!(
+                    "{} {} does not fit in {} digits of the version code",
+                    name, value, digits
+                ));
+            }
+
+            code = code
+                .checked_mul(limit)
+                .and_then(|c| c.checked_add(u64::from(value)))
+                .filter(|c| *c <= MAX_VERSION_CODE)
+                .ok_or_else(|| format!("version code for {} is larger than {}", version, MAX_VERSION_CODE))?;
+        }
+
+        Ok(code)
+    }
+
+    /// Render the numeric components of a version zero-padded to a fixed width, e.g. `001.004.012`,
+    /// so that versions sort correctly as strings.
+    pub fn sortable_version(&self, version: &Version) -> Result<String, String> {
+        let mut components = vec![version.major, version.minor, version.patch];
+        components.extend(version.revision);
+
+        let width = self.sortable_digits as usize;
+
+        let components = components
+            .into_iter()
+            .map(|c| {
+                let s = format!("{:0width$}", c, width = width);
+
+                if s.len() > width {
+                    Err(format!(
+                        "version component {} does not fit in {} digits of the sortable version",
+                        c, width
+                    ))
+                } else {
+                    Ok(s)
+                }
+            })
+            .collect::<Result<Vec<String>, String>>()?;
+
+        Ok(components.join("."))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::version::ParseMode;
+
+    #[test]
+    /// Test encoding versions as a version code
+    fn test_version_code() {
+        let format = NumericVersionFormat::default();
+
+        assert_eq!(format.version_code(&"1.4.12".parse().unwrap()), Ok(10412));
+        assert_eq!(format.version_code(&"0.0.1".parse().unwrap()), Ok(1));
+        assert_eq!(format.version_code(&"0.0.1+build.5".parse().unwrap()), Ok(1));
+        assert!(format.version_code(&"0.0.1-beta.1".parse().unwrap()).is_err());
+        assert!(format.version_code(&"1.100.0".parse().unwrap()).is_err());
+        assert!(format.version_code(&"300000.0.0".parse().unwrap()).is_err());
+
+        let format = NumericVersionFormat {
+            minor_digits: 3,
+            patch_digits: 1,
+            revision_digits: Some(3),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            format.version_code(&Version::parse("2.15.3.42", ParseMode::STRICT.with_revision(true)).unwrap()),
+            Ok(20153042)
+        );
+        assert_eq!(format.version_code(&"2.15.3".parse().unwrap()), Ok(20153000));
+    }
+
+    #[test]
+    /// Test rendering zero-padded sortable versions
+    fn test_sortable_version() {
+        let format = NumericVersionFormat::default();
+
+        assert_eq!(
+            format.sortable_version(&"1.4.12".parse().unwrap()),
+            Ok("001.004.012".to_owned())
+        );
+        assert_eq!(
+            format.sortable_version(&Version::parse("1.4.12.7", ParseMode::STRICT.with_revision(true)).unwrap()),
+            Ok("001.004.012.007".to_owned())
+        );
+        assert!(format.sortable_version(&"1000.0.0".parse().unwrap()).is_err());
+    }
+}
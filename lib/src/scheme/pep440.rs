@@ -0,0 +1,320 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use tracing::warn;
+
+use crate::version::{self, Version};
+
+static REGEX_PARSE_PEP440: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?xi)
+        ^\s*v?
+        (?:(?P<epoch>[0-9]+)!)?
+        (?P<release>[0-9]+(?:\.[0-9]+)*)
+        (?P<pre>[-_\.]?(?P<pre_l>alpha|a|beta|b|preview|pre|rc|c)[-_\.]?(?P<pre_n>[0-9]+)?)?
+        (?P<post>(?:-(?P<post_n1>[0-9]+))|(?:[-_\.]?(?P<post_l>post|rev|r)[-_\.]?(?P<post_n2>[0-9]+)?))?
+        (?P<dev>[-_\.]?(?P<dev_l>dev)[-_\.]?(?P<dev_n>[0-9]+)?)?
+        (?:\+(?P<local>[a-z0-9]+(?:[-_\.][a-z0-9]+)*))?
+        \s*$"#,
+    )
+    .unwrap()
+});
+static REGEX_SPLIT_SEMVER_PRERELEASE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)^(?:(alpha|a|beta|b|preview|pre|rc|c)(?:[\.-]?([0-9]+))?)?(?:[\.-]?(dev)(?:[\.-]?([0-9]+))?)?$"#)
+        .unwrap()
+});
+static REGEX_SPLIT_SEMVER_BUILD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)^post[\.-]?([0-9]+)(?:[\.-](.+))?$"#).unwrap());
+
+/// The kind of a PEP 440 prerelease.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Pep440PreKind {
+    Alpha,
+    Beta,
+    ReleaseCandidate,
+}
+
+impl Pep440PreKind {
+    fn from_label(label: &str) -> Self {
+        match label.to_ascii_lowercase().as_str() {
+            "a" | "alpha" => Self::Alpha,
+            "b" | "beta" => Self::Beta,
+            _ => Self::ReleaseCandidate,
+        }
+    }
+
+    /// Get the normalized PEP 440 label, e.g. "rc".
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Alpha => "a",
+            Self::Beta => "b",
+            Self::ReleaseCandidate => "rc",
+        }
+    }
+
+    /// Get the equivalent SemVer prerelease channel, e.g. "beta".
+    fn semver_channel(&self) -> &'static str {
+        match self {
+            Self::Alpha => "alpha",
+            Self::Beta => "beta",
+            Self::ReleaseCandidate => "rc",
+        }
+    }
+}
+
+/// Structure representing a PEP 440 compliant version, as used by Python packages.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pep440Version {
+    pub epoch: u32,
+    pub release: Vec<u32>,
+    pub pre: Option<(Pep440PreKind, u32)>,
+    pub post: Option<u32>,
+    pub dev: Option<u32>,
+    pub local: Option<String>,
+}
+
+impl Pep440Version {
+    /// Convert a SemVer version to its closest PEP 440 equivalent.
+    ///
+    /// Prereleases in the alpha, beta and rc channels become PEP 440 prereleases,
+    /// a "dev" prerelease becomes a development release and build metadata of the form
+    /// "post.N" becomes a post-release. Any other build metadata becomes a local version.
+    pub fn from_version(version: &Version) -> Self {
+        let mut pre = None;
+        let mut dev = None;
+
+        if let Some(prerelease) = &version.prerelease {
+            match REGEX_SPLIT_SEMVER_PRERELEASE.captures(prerelease) {
+                Some(cap) => {
+                    pre = cap
+                        .get(1)
+                        .map(|m| (Pep440PreKind::from_label(m.as_str()), parse_number(cap.get(2))));
+                    dev = cap.get(3).map(|_| parse_number(cap.get(4)));
+                }
+                None => {
+                    // PEP 440 has no equivalent of arbitrary prereleases, so fall back to a development release.
+                    let number = version::split_numbered_prerelease(prerelease).map_or(0, |(_, n)| n);
+
+                    warn!("Prerelease '{prerelease}' has no PEP 440 equivalent. Using development release {number}.");
+
+                    dev = Some(number);
+                }
+            }
+        }
+
+        let (post, local) = match &version.build {
+            Some(build) => match REGEX_SPLIT_SEMVER_BUILD.captures(build) {
+                Some(cap) => (
+                    Some(parse_number(cap.get(1))),
+                    cap.get(2).map(|m| normalize_local(m.as_str())),
+                ),
+                None => (None, Some(normalize_local(build))),
+            },
+            None => (None, None),
+        };
+
+        Self {
+            epoch: 0,
+            release: [version.major, version.minor, version.patch]
+                .into_iter()
+                .chain(version.revision)
+                .collect(),
+            pre,
+            post,
+            dev,
+            local: local.filter(|l| !l.is_empty()),
+        }
+    }
+
+    /// Convert to the equivalent SemVer version.
+    /// This is the inverse of `from_version`.
+    pub fn to_version(&self) -> Result<Version, String> {
+        if self.epoch != 0 {
+            return Err(format!("PEP 440 version '{self}' has an epoch, which is not supported"));
+        }
+
+        if self.release.len() > 4 {
+            return Err(format!(
+                "PEP 440 version '{self}' has more than four release components, which is not supported"
+            ));
+        }
+
+        let release_component = |i: usize| self.release.get(i).copied().unwrap_or(0);
+
+        let mut prerelease: Vec<String> = Vec::new();
+
+        if let Some((kind, number)) = self.pre {
+            prerelease.push(format!("{}.{}", kind.semver_channel(), number));
+        }
+
+        if let Some(dev) = self.dev {
+            prerelease.push(format!("dev.{}", dev));
+        }
+
+        let mut build: Vec<String> = Vec::new();
+
+        if let Some(post) = self.post {
+            build.push(format!("post.{}", post));
+        }
+
+        if let Some(local) = &self.local {
+            build.push(local.clone());
+        }
+
+        Ok(Version {
+            major: release_component(0),
+            minor: release_component(1),
+            patch: release_component(2),
+            revision: self.release.get(3).copied(),
+            prerelease: Some(prerelease.join(".")).filter(|p| !p.is_empty()),
+            build: Some(build.join(".")).filter(|b| !b.is_empty()),
+        })
+    }
+}
+
+impl FromStr for Pep440Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid PEP 440 version string: {}", s);
+
+        let cap = REGEX_PARSE_PEP440.captures(s).ok_or_else(invalid)?;
+
+        let number = |name: &str| -> Result<Option<u32>, String> {
+            cap.name(name)
+                .map(|m| m.as_str().parse().map_err(|_| invalid()))
+                .transpose()
+        };
+
+        let release = cap["release"]
+            .split('.')
+            .map(|n| n.parse().map_err(|_| invalid()))
+            .collect::<Result<Vec<u32>, String>>()?;
+
+        let pre = match cap.name("pre_l") {
+            Some(label) => Some((Pep440PreKind::from_label(label.as_str()), number("pre_n")?.unwrap_or(0))),
+            None => None,
+        };
+
+        let post = if cap.name("post").is_some() {
+            Some(number("post_n1")?.or(number("post_n2")?).unwrap_or(0))
+        } else {
+            None
+        };
+
+        let dev = if cap.name("dev").is_some() {
+            Some(number("dev_n")?.unwrap_or(0))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            epoch: number("epoch")?.unwrap_or(0),
+            release,
+            pre,
+            post,
+            dev,
+            local: cap.name("local").map(|m| normalize_local(m.as_str())),
+        })
+    }
+}
+
+impl fmt::Display for Pep440Version {
+    /// Format as a normalized PEP 440 version string.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+
+        let release: Vec<String> = self.release.iter().map(|n| n.to_string()).collect();
+        write!(f, "{}", release.join("."))?;
+
+        if let Some((kind, number)) = self.pre {
+            write!(f, "{}{}", kind.label(), number)?;
+        }
+
+        if let Some(post) = self.post {
+            write!(f, ".post{}", post)?;
+        }
+
+        if let Some(dev) = self.dev {
+            write!(f, ".dev{}", dev)?;
+        }
+
+        if let Some(local) = &self.local {
+            write!(f, "+{}", local)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_number(m: Option<regex::Match>) -> u32 {
+    m.and_then(|m| m.as_str().parse().ok()).unwrap_or(0)
+}
+
+/// Normalize a local version label, using only lowercase alphanumerics separated by periods.
+fn normalize_local(s: &str) -> String {
+    s.split(['-', '_', '.'])
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Test that PEP 440 versions round-trip through SemVer versions
+    fn test_round_trip() {
+        for s in [
+            "1.2.0",
+            "1.2.0rc1",
+            "1.2.0a2",
+            "1.2.0b3.dev1",
+            "1.2.0.post3",
+            "2.0.0.dev4",
+            "1.2.0rc1.post2.dev3",
+            "1.2.0+ubuntu.1",
+            "1.2.0.post1+local.7",
+            "1.2.0.4rc1",
+        ] {
+            let version = s.parse::<Pep440Version>().unwrap().to_version().unwrap();
+
+            assert_eq!(Pep440Version::from_version(&version).to_string(), s);
+        }
+    }
+
+    #[test]
+    /// Test parsing and normalization of non-normalized PEP 440 versions
+    fn test_parse() {
+        assert_eq!("v1.2-RC.1".parse::<Pep440Version>().unwrap().to_string(), "1.2rc1");
+        assert_eq!("1.2.0-1".parse::<Pep440Version>().unwrap().to_string(), "1.2.0.post1");
+        assert_eq!(
+            "1.2.0preview2".parse::<Pep440Version>().unwrap().to_string(),
+            "1.2.0rc2"
+        );
+        assert_eq!("1!1.2.0".parse::<Pep440Version>().unwrap().to_string(), "1!1.2.0");
+        assert!("1.2.0garbage".parse::<Pep440Version>().is_err());
+        assert!("1!1.2.0".parse::<Pep440Version>().unwrap().to_version().is_err());
+    }
+
+    #[test]
+    /// Test conversion of SemVer versions to PEP 440
+    fn test_from_version() {
+        let pep440 = |s: &str| Pep440Version::from_version(&s.parse().unwrap()).to_string();
+
+        assert_eq!(pep440("1.2.0-rc.1"), "1.2.0rc1");
+        assert_eq!(pep440("1.2.0-beta.2"), "1.2.0b2");
+        assert_eq!(pep440("1.2.0-alpha"), "1.2.0a0");
+        assert_eq!(pep440("2.0.0-dev.4"), "2.0.0.dev4");
+        assert_eq!(pep440("1.2.0-alpha.1.dev.2"), "1.2.0a1.dev2");
+        assert_eq!(pep440("1.2.0-nightly.5"), "1.2.0.dev5");
+        assert_eq!(pep440("1.2.0+build-42"), "1.2.0+build.42");
+        assert_eq!(pep440("1.2.0+post.3"), "1.2.0.post3");
+    }
+}
@@ -8,10 +8,14 @@ use tracing::info;
 use crate::util;
 
 pub mod processor;
+mod version_input;
 
 #[derive(Debug, Default)]
 pub struct TemplateInput {
     pub values: HashMap<String, String>,
+    /// Variables that exist, but cannot be computed for the current version,
+    /// along with the reason why. Using one of these in a template is an error.
+    pub unavailable: HashMap<String, String>,
 }
 
 impl TemplateInput {
@@ -21,6 +25,19 @@ impl TemplateInput {
 
     pub fn merge_from(&mut self, other: Self) {
         self.values.extend(other.values);
+        self.unavailable.extend(other.unavailable);
+    }
+
+    /// Insert a value if it could be computed, or mark the variable as unavailable otherwise.
+    pub fn insert_result(&mut self, name: &str, value: Result<String, String>) {
+        match value {
+            Ok(value) => {
+                self.values.insert(name.to_owned(), value);
+            }
+            Err(reason) => {
+                self.unavailable.insert(name.to_owned(), reason);
+            }
+        }
     }
 }
 
@@ -14,6 +14,7 @@ impl TemplateProcessor for VutProcessor {
         let variables = &values.values;
 
         let mut variables_not_found: Vec<String> = Vec::new();
+        let mut variables_unavailable: Vec<String> = Vec::new();
 
         let output = REGEX_FIND_TEMPLATE_VARS.replace_all(template, |captures: &regex::Captures| {
             let prefix = captures.get(1).map(|v| v.as_str()).unwrap_or("");
@@ -22,6 +23,10 @@ impl TemplateProcessor for VutProcessor {
 
             let variable_value = if let Some(value) = variables.get(variable_name) {
                 value
+            } else if let Some(reason) = values.unavailable.get(variable_name) {
+                variables_unavailable.push(format!("{} ({})", variable_name, reason));
+
+                ""
             } else {
                 variables_not_found.push(variable_name.to_owned());
 
@@ -41,6 +46,10 @@ impl TemplateProcessor for VutProcessor {
             return Err(format!("Variables not found: {}", variables_not_found.join(", ")));
         }
 
+        if !variables_unavailable.is_empty() {
+            return Err(format!("Variables unavailable: {}", variables_unavailable.join(", ")));
+        }
+
         Ok(output)
     }
 }
@@ -57,7 +66,14 @@ mod test {
         variables.insert("TheVariable".to_owned(), "42".to_owned());
         variables.insert("EmptyVariable".to_owned(), "".to_owned());
 
-        TemplateInput { values: variables }
+        let mut unavailable: HashMap<String, String> = HashMap::new();
+
+        unavailable.insert("UnavailableVariable".to_owned(), "some reason".to_owned());
+
+        TemplateInput {
+            values: variables,
+            unavailable,
+        }
     }
 
     macro_rules! test_processor {
@@ -103,6 +119,7 @@ mod test {
 
             err {
                 "BLAH={{NonExistentVariable}};" => "Variables not found: NonExistentVariable"
+                "BLAH={{UnavailableVariable}};" => "Variables unavailable: UnavailableVariable (some reason)"
             }
         }
     }
@@ -0,0 +1,103 @@
+use crate::scheme::{Pep440Version, ecosystem};
+use crate::template::TemplateInput;
+use crate::version::{self, Version};
+
+impl TemplateInput {
+    /// Generate the template variables that only depend on the version, and not on the configuration.
+    pub fn from_version(version: &Version) -> Self {
+        let mut template_input = Self::new();
+        let values = &mut template_input.values;
+
+        let split_prerelease = version
+            .prerelease
+            .as_ref()
+            .and_then(|p| version::split_numbered_prerelease(p));
+        let split_build = version
+            .build
+            .as_ref()
+            .and_then(|b| version::split_numbered_prerelease(b));
+
+        values.insert("FullVersion".to_owned(), version.to_string());
+        values.insert(
+            "Version".to_owned(),
+            Version {
+                build: None,
+                ..version.clone()
+            }
+            .to_string(),
+        );
+        values.insert(
+            "MajorMinorPatch".to_owned(),
+            format!("{}.{}.{}", version.major, version.minor, version.patch),
+        );
+        values.insert("MajorMinor".to_owned(), format!("{}.{}", version.major, version.minor));
+        values.insert("Major".to_owned(), format!("{}", version.major));
+        values.insert("Minor".to_owned(), format!("{}", version.minor));
+        values.insert("Patch".to_owned(), format!("{}", version.patch));
+        values.insert(
+            "Prerelease".to_owned(),
+            version.prerelease.as_ref().map_or("", |p| p).to_owned(),
+        );
+        values.insert(
+            "PrereleasePrefix".to_owned(),
+            split_prerelease
+                .map(|sp| sp.0.to_owned())
+                .unwrap_or_else(|| "".to_owned()),
+        );
+        values.insert(
+            "PrereleaseNumber".to_owned(),
+            split_prerelease
+                .map(|sp| format!("{}", sp.1))
+                .unwrap_or_else(|| "".to_owned()),
+        );
+        values.insert("Build".to_owned(), version.build.as_ref().map_or("", |b| b).to_owned());
+        values.insert(
+            "BuildPrefix".to_owned(),
+            split_build.map(|sp| sp.0.to_owned()).unwrap_or_else(|| "".to_owned()),
+        );
+        values.insert(
+            "BuildNumber".to_owned(),
+            split_build
+                .map(|sp| format!("{}", sp.1))
+                .unwrap_or_else(|| "".to_owned()),
+        );
+
+        values.insert(
+            "Revision".to_owned(),
+            version.revision.map_or_else(|| "".to_owned(), |r| format!("{}", r)),
+        );
+        values.insert(
+            "Pep440Version".to_owned(),
+            Pep440Version::from_version(version).to_string(),
+        );
+
+        // Renderings for various packaging ecosystems
+        values.insert("DebianVersion".to_owned(), ecosystem::debian_version(version));
+        values.insert("RpmVersion".to_owned(), ecosystem::rpm_version(version));
+        values.insert("RpmRelease".to_owned(), ecosystem::rpm_release(version));
+        values.insert("NuGetVersion".to_owned(), ecosystem::nuget_version(version));
+        values.insert("DockerTag".to_owned(), ecosystem::docker_tag(version));
+        values.insert("MavenVersion".to_owned(), ecosystem::maven_version(version));
+        values.insert(
+            "MavenSnapshotVersion".to_owned(),
+            ecosystem::maven_snapshot_version(version),
+        );
+        values.insert("GemVersion".to_owned(), ecosystem::gem_version(version));
+
+        // Four-part versions are used by Windows and .NET, which require each component to fit in 16 bits.
+        let four_part = ecosystem::windows_version_components(version);
+
+        template_input.insert_result(
+            "FourPartVersion",
+            four_part
+                .clone()
+                .map(|c| format!("{}.{}.{}.{}", c[0], c[1], c[2], c[3])),
+        );
+        template_input.insert_result(
+            "FileVersionCommas",
+            four_part.map(|c| format!("{},{},{},{}", c[0], c[1], c[2], c[3])),
+        );
+
+        template_input
+    }
+}
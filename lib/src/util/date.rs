@@ -0,0 +1,109 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A calendar date in the proleptic Gregorian calendar.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        Self { year, month, day }
+    }
+
+    /// Get the current date in UTC.
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        Self::from_days_since_epoch(seconds.div_euclid(86400))
+    }
+
+    /// Construct a date from the number of days since 1970-01-01.
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        // Algorithm from Howard Hinnant's "chrono-Compatible Low-Level Date Algorithms".
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+
+        Self { year, month, day }
+    }
+
+    /// Get the number of days since 1970-01-01.
+    pub fn days_since_epoch(&self) -> i64 {
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let mp = (self.month as i64 + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        era * 146097 + doe - 719468
+    }
+
+    /// Get the ISO 8601 weekday, from 1 (Monday) to 7 (Sunday).
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday.
+        ((self.days_since_epoch() + 3).rem_euclid(7) + 1) as u32
+    }
+
+    /// Get the ISO 8601 week-numbering year and week number.
+    pub fn iso_week(&self) -> (i32, u32) {
+        let ordinal = (self.days_since_epoch() - Self::new(self.year, 1, 1).days_since_epoch() + 1) as i32;
+        let week = (ordinal - self.weekday() as i32 + 10) / 7;
+
+        if week < 1 {
+            (self.year - 1, weeks_in_year(self.year - 1))
+        } else if week as u32 > weeks_in_year(self.year) {
+            (self.year + 1, 1)
+        } else {
+            (self.year, week as u32)
+        }
+    }
+}
+
+/// Get the number of ISO 8601 weeks in a year.
+fn weeks_in_year(year: i32) -> u32 {
+    let is_leap_year = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let jan1_weekday = Date::new(year, 1, 1).weekday();
+
+    // Years starting on a Thursday, and leap years starting on a Wednesday, have 53 weeks.
+    if jan1_weekday == 4 || (is_leap_year && jan1_weekday == 3) {
+        53
+    } else {
+        52
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Test conversion to and from days since epoch
+    fn test_days_since_epoch() {
+        assert_eq!(Date::from_days_since_epoch(0), Date::new(1970, 1, 1));
+        assert_eq!(Date::from_days_since_epoch(19860), Date::new(2024, 5, 17));
+        assert_eq!(Date::new(2024, 2, 29).days_since_epoch(), 19782);
+        assert_eq!(Date::from_days_since_epoch(-1), Date::new(1969, 12, 31));
+    }
+
+    #[test]
+    /// Test ISO week numbers, including weeks belonging to adjacent years
+    fn test_iso_week() {
+        assert_eq!(Date::new(2024, 5, 17).iso_week(), (2024, 20));
+        assert_eq!(Date::new(2021, 1, 1).iso_week(), (2020, 53));
+        assert_eq!(Date::new(2024, 12, 30).iso_week(), (2025, 1));
+        assert_eq!(Date::new(2026, 1, 1).iso_week(), (2026, 1));
+    }
+}
@@ -1,8 +1,9 @@
 use std::fmt;
 use std::fs;
-use std::io;
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 
+use crate::project::VutError;
 use crate::util;
 
 #[derive(Debug)]
@@ -52,3 +53,23 @@ pub fn open_file(path: impl AsRef<Path>) -> Result<fs::File, FileError> {
         path: util::normalize_path(path),
     })
 }
+
+/// Read the contents of a file containing a version, such as a project manifest.
+pub fn read_version_file(path: impl AsRef<Path>) -> Result<String, VutError> {
+    let mut file = open_file(path).map_err(VutError::VersionFileOpen)?;
+
+    let mut text = String::new();
+
+    file.read_to_string(&mut text).map_err(VutError::VersionFileRead)?;
+
+    Ok(text)
+}
+
+/// Overwrite a file containing a version, such as a project manifest.
+pub fn write_version_file(path: impl AsRef<Path>, text: &str) -> Result<(), VutError> {
+    let mut file = create_file(path).map_err(VutError::VersionFileOpen)?;
+
+    file.write_all(text.as_bytes()).map_err(VutError::VersionFileWrite)?;
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+//! Format-preserving editing of JSON documents.
+//!
+//! Instead of round-tripping a document through a JSON serializer, these functions locate the
+//! exact span of a value in the original text, so that it can be replaced without affecting
+//! indentation, key order, line endings or anything else in the document.
+//!
+//! The document is assumed to be valid JSON, which should be verified beforehand,
+//! for example by parsing it with serde_json.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::project::VutError;
+
+/// Parse a JSON document, to read values from it or to verify that it is valid before editing it.
+pub fn parse_json(json_str: &str) -> Result<serde_json::Value, VutError> {
+    serde_json::from_str(json_str).map_err(|err| VutError::Other(Cow::Owned(err.to_string())))
+}
+
+/// Find the byte range of the value at the specified path of object keys.
+/// Returns None if any key in the path does not exist.
+pub fn find_json_value(json: &str, path: &[&str]) -> Option<Range<usize>> {
+    let bytes = json.as_bytes();
+
+    let mut start = skip_whitespace(bytes, 0);
+    let mut end = skip_value(bytes, start)?;
+
+    for key in path {
+        let (value_start, value_end) = find_object_value(json, start, key)?;
+
+        start = value_start;
+        end = value_end;
+    }
+
+    Some(start..end)
+}
+
+/// Replace the value at the specified path of object keys with a string,
+/// leaving the rest of the document untouched.
+/// Returns None if any key in the path does not exist.
+pub fn set_json_string(json: &str, path: &[&str], value: &str) -> Option<String> {
+    let range = find_json_value(json, path)?;
+
+    // Serializing a string cannot fail
+    let value = serde_json::to_string(value).unwrap();
+
+    let mut new_json = String::with_capacity(json.len() + value.len());
+    new_json.push_str(&json[..range.start]);
+    new_json.push_str(&value);
+    new_json.push_str(&json[range.end..]);
+
+    Some(new_json)
+}
+
+/// Find the range of the value with the specified key in the object starting at the specified position.
+fn find_object_value(json: &str, position: usize, key: &str) -> Option<(usize, usize)> {
+    let bytes = json.as_bytes();
+
+    if bytes.get(position) != Some(&b'{') {
+        return None;
+    }
+
+    let mut i = skip_whitespace(bytes, position + 1);
+
+    while bytes.get(i) == Some(&b'"') {
+        let key_end = skip_string(bytes, i)?;
+        let this_key: String = serde_json::from_str(&json[i..key_end]).ok()?;
+
+        // Skip colon
+        i = skip_whitespace(bytes, key_end);
+        i = skip_whitespace(bytes, i + 1);
+
+        let value_end = skip_value(bytes, i)?;
+
+        if this_key == key {
+            return Some((i, value_end));
+        }
+
+        // Skip comma
+        i = skip_whitespace(bytes, value_end);
+        if bytes.get(i) == Some(&b',') {
+            i = skip_whitespace(bytes, i + 1);
+        }
+    }
+
+    None
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && matches!(bytes[i], b' ' | b'\t' | b'\r' | b'\n') {
+        i += 1;
+    }
+
+    i
+}
+
+/// Skip a string starting at the specified position, returning the position after the closing quote.
+fn skip_string(bytes: &[u8], mut i: usize) -> Option<usize> {
+    i += 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+
+    None
+}
+
+/// Skip a value starting at the specified position, returning the position after it.
+fn skip_value(bytes: &[u8], i: usize) -> Option<usize> {
+    match bytes.get(i)? {
+        b'"' => skip_string(bytes, i),
+        b'{' | b'[' => {
+            let mut depth = 0;
+            let mut i = i;
+
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = skip_string(bytes, i)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+
+                i += 1;
+            }
+
+            None
+        }
+        // Numbers and literals
+        _ => {
+            let mut i = i;
+
+            while i < bytes.len() && !matches!(bytes[i], b',' | b'}' | b']' | b' ' | b'\t' | b'\r' | b'\n') {
+                i += 1;
+            }
+
+            Some(i)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const JSON: &str = "{\r\n\t\"name\": \"a\\\"b\",\r\n\t\"nested\": { \"version\": [1, {\"x\": \"}\"}] },\r\n\t\"version\": \"1.0.0\"\r\n}\r\n";
+
+    #[test]
+    /// Test finding values at a path
+    fn test_find_json_value() {
+        let range = find_json_value(JSON, &["version"]).unwrap();
+        assert_eq!(&JSON[range], "\"1.0.0\"");
+
+        let range = find_json_value(JSON, &["nested", "version"]).unwrap();
+        assert_eq!(&JSON[range], "[1, {\"x\": \"}\"}]");
+
+        assert_eq!(find_json_value(JSON, &["missing"]), None);
+        assert_eq!(find_json_value(JSON, &["name", "version"]), None);
+    }
+
+    #[test]
+    /// Test that replacing a value leaves the rest of the document untouched
+    fn test_set_json_string() {
+        assert_eq!(
+            set_json_string(JSON, &["version"], "2.0.0").unwrap(),
+            JSON.replace("1.0.0", "2.0.0")
+        );
+        assert_eq!(set_json_string(JSON, &["missing"], "2.0.0"), None);
+    }
+}
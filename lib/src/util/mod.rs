@@ -1,9 +1,25 @@
 mod config;
+mod date;
 mod fs;
+mod json;
 mod path;
+mod properties;
+#[cfg(test)]
+mod testdir;
 mod textfile;
+mod tomldoc;
+mod xml;
+mod yaml;
 
 pub use config::*;
+pub use date::*;
 pub use fs::*;
+pub use json::*;
 pub use path::*;
+pub use properties::*;
+#[cfg(test)]
+pub use testdir::*;
 pub use textfile::*;
+pub use tomldoc::*;
+pub use xml::*;
+pub use yaml::*;
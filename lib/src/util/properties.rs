@@ -0,0 +1,226 @@
+//! Format-preserving editing of Java properties files, as read by `java.util.Properties`.
+//!
+//! Properties are located by their exact span in the original text, so that a value can be
+//! replaced without affecting comments, blank lines, ordering or other properties.
+//! Keys and values are unescaped when read and escaped when written, and values may span
+//! multiple lines using continuation lines ending in a backslash.
+
+use std::ops::Range;
+
+/// Find the byte range of the raw, escaped value of the last property with the specified key,
+/// as the last one takes precedence when loaded.
+/// Continuation lines belonging to the value are included in the range.
+pub fn find_property(text: &str, key: &str) -> Option<Range<usize>> {
+    parse_properties(text)
+        .into_iter()
+        .rev()
+        .find(|(k, _)| k == key)
+        .map(|(_, range)| range)
+}
+
+/// Get the unescaped value of the property with the specified key.
+pub fn get_property(text: &str, key: &str) -> Option<String> {
+    find_property(text, key).map(|range| unescape_property(&text[range]))
+}
+
+/// Replace the value of the property with the specified key, leaving the rest of the file untouched.
+/// Returns None if the property does not exist.
+pub fn set_property(text: &str, key: &str, value: &str) -> Option<String> {
+    let range = find_property(text, key)?;
+
+    let value = escape_property_value(value);
+
+    let mut new_text = String::with_capacity(text.len() + value.len());
+    new_text.push_str(&text[..range.start]);
+    new_text.push_str(&value);
+    new_text.push_str(&text[range.end..]);
+
+    Some(new_text)
+}
+
+/// Parse all properties, returning their unescaped keys and the ranges of their raw values.
+fn parse_properties(text: &str) -> Vec<(String, Range<usize>)> {
+    let bytes = text.as_bytes();
+
+    let mut properties = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        i = skip_whitespace(bytes, i);
+
+        match bytes.get(i) {
+            None => break,
+            // Blank line
+            Some(b'\r' | b'\n') => {
+                i += 1;
+                continue;
+            }
+            // Comment line
+            Some(b'#' | b'!') => {
+                i = line_end(bytes, i);
+                continue;
+            }
+            _ => {}
+        }
+
+        // Key, up to the first unescaped separator or whitespace
+        let key_start = i;
+
+        while i < bytes.len() && !matches!(bytes[i], b'=' | b':' | b' ' | b'\t' | b'\x0c' | b'\r' | b'\n') {
+            i += if bytes[i] == b'\\' { 2 } else { 1 };
+        }
+
+        let key_end = i.min(bytes.len());
+
+        // Separator, optionally surrounded by whitespace
+        i = skip_whitespace(bytes, key_end);
+        if matches!(bytes.get(i), Some(b'=' | b':')) {
+            i = skip_whitespace(bytes, i + 1);
+        }
+
+        // Value, up to the end of the logical line
+        let value_start = i.min(bytes.len());
+        let value_end = logical_line_end(bytes, value_start);
+
+        properties.push((unescape_property(&text[key_start..key_end]), value_start..value_end));
+
+        i = value_end;
+    }
+
+    properties
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && matches!(bytes[i], b' ' | b'\t' | b'\x0c') {
+        i += 1;
+    }
+
+    i
+}
+
+/// Find the end of the natural line containing the specified position, excluding the line terminator.
+fn line_end(bytes: &[u8], i: usize) -> usize {
+    bytes[i..]
+        .iter()
+        .position(|b| matches!(b, b'\r' | b'\n'))
+        .map_or(bytes.len(), |n| i + n)
+}
+
+/// Find the end of the logical line containing the specified position, following continuation lines.
+fn logical_line_end(bytes: &[u8], mut i: usize) -> usize {
+    loop {
+        let end = line_end(bytes, i);
+
+        // A line is continued if it ends with an odd number of backslashes
+        let backslashes = bytes[i..end].iter().rev().take_while(|b| **b == b'\\').count();
+
+        if backslashes % 2 == 0 || end == bytes.len() {
+            return end;
+        }
+
+        i = end + 1;
+        if bytes[end] == b'\r' && bytes.get(i) == Some(&b'\n') {
+            i += 1;
+        }
+    }
+}
+
+/// Unescape a raw key or value, joining continuation lines.
+pub fn unescape_property(raw: &str) -> String {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => value.push('\t'),
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('f') => value.push('\x0c'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+
+                if let Some(c) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    value.push(c);
+                }
+            }
+            // Continuation line, whose leading whitespace is ignored
+            Some(c @ ('\r' | '\n')) => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+
+                while chars.next_if(|c| matches!(c, ' ' | '\t' | '\x0c')).is_some() {}
+            }
+            Some(c) => value.push(c),
+            None => {}
+        }
+    }
+
+    value
+}
+
+/// Escape a value, the same way as `java.util.Properties` stores it.
+pub fn escape_property_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\x0c' => escaped.push_str("\\f"),
+            ' ' if i == 0 => escaped.push_str("\\ "),
+            '=' | ':' | '#' | '!' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if !(' '..='~').contains(&c) => {
+                let mut buf = [0; 2];
+
+                for unit in c.encode_utf16(&mut buf) {
+                    escaped.push_str(&format!("\\u{:04X}", unit));
+                }
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PROPERTIES: &str = "# version=0.0.1\r\n! Comment\r\n\r\norg.gradle.jvmargs = -Xmx2g \\\r\n    -Dversion=0.0.2\r\nversion:1.0.0\r\n  group   com.example\r\nname=a\\\\\nversion = 1.\\\n    2.\\u0030-beta\n";
+
+    #[test]
+    /// Test reading properties, including escapes and continuation lines
+    fn test_get_property() {
+        assert_eq!(get_property(PROPERTIES, "version").as_deref(), Some("1.2.0-beta"));
+        assert_eq!(get_property(PROPERTIES, "group").as_deref(), Some("com.example"));
+        assert_eq!(get_property(PROPERTIES, "name").as_deref(), Some("a\\"));
+        assert_eq!(
+            get_property(PROPERTIES, "org.gradle.jvmargs").as_deref(),
+            Some("-Xmx2g -Dversion=0.0.2")
+        );
+        assert_eq!(get_property(PROPERTIES, "missing"), None);
+    }
+
+    #[test]
+    /// Test that replacing a value leaves the rest of the file untouched
+    fn test_set_property() {
+        assert_eq!(
+            set_property(PROPERTIES, "version", "1.3.0").unwrap(),
+            PROPERTIES.replace("1.\\\n    2.\\u0030-beta", "1.3.0")
+        );
+        assert_eq!(set_property("a=b\n", "a", " x:\u{e9}").unwrap(), "a=\\ x\\:\\u00E9\n");
+        assert_eq!(set_property(PROPERTIES, "missing", "1.3.0"), None);
+    }
+}
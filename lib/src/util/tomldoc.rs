@@ -0,0 +1,27 @@
+/// Replace a string value in a TOML document, preserving any surrounding whitespace and comments.
+pub fn set_toml_string(item: &mut toml_edit::Item, s: &str) {
+    let decor = item.as_value().map(|v| v.decor().clone());
+
+    *item = toml_edit::value(s);
+
+    if let (Some(decor), Some(value)) = (decor, item.as_value_mut()) {
+        *value.decor_mut() = decor;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Test that replacing a value keeps its comment and alignment
+    fn test_set_toml_string() {
+        let mut doc = "[project]\nversion   = \"1.0.0\"  # managed by vut\n"
+            .parse::<toml_edit::DocumentMut>()
+            .unwrap();
+
+        set_toml_string(&mut doc["project"]["version"], "1.1.0");
+
+        assert_eq!(doc.to_string(), "[project]\nversion   = \"1.1.0\"  # managed by vut\n");
+    }
+}
@@ -0,0 +1,275 @@
+//! Format-preserving editing of XML documents.
+//!
+//! Like the JSON functions, these locate the exact span of an element's text in the original
+//! document, so that it can be replaced without affecting indentation, comments, attribute
+//! order or anything else in the document.
+//!
+//! Elements are matched by their local name, ignoring any namespace prefix.
+//! The scanner is lenient, and only as strict as needed to find elements in well-formed documents.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// Find the byte ranges of the text of all elements at the specified path of element names,
+/// starting from the root element, in document order.
+/// Surrounding whitespace is not included in the ranges.
+/// Self-closing elements have no text, and are not included.
+pub fn find_xml_elements(xml: &str, path: &[&str]) -> Vec<Range<usize>> {
+    let bytes = xml.as_bytes();
+
+    let mut ranges = Vec::new();
+    let mut stack: Vec<(&str, usize)> = Vec::new();
+    let mut i = 0;
+
+    while let Some(offset) = xml[i..].find('<') {
+        let tag_start = i + offset;
+        let rest = &xml[tag_start..];
+
+        let tag_end = if rest.starts_with("<!--") {
+            find_end(xml, tag_start, "-->")
+        } else if rest.starts_with("<![CDATA[") {
+            find_end(xml, tag_start, "]]>")
+        } else if rest.starts_with("<?") {
+            find_end(xml, tag_start, "?>")
+        } else if rest.starts_with("<!") {
+            skip_declaration(bytes, tag_start)
+        } else if rest.starts_with("</") {
+            let Some(tag_end) = find_end(xml, tag_start, ">") else {
+                break;
+            };
+
+            let name = local_name(xml[tag_start + 2..tag_end - 1].trim());
+
+            // Pop up to and including the matching element, to tolerate unclosed elements
+            if let Some(index) = stack.iter().rposition(|(n, _)| *n == name) {
+                if index + 1 == path.len() && stack.iter().zip(path).all(|((n, _), p)| n == p) {
+                    ranges.push(trim_range(xml, stack[index].1..tag_start));
+                }
+
+                stack.truncate(index);
+            }
+
+            Some(tag_end)
+        } else {
+            let Some(tag_end) = skip_start_tag(bytes, tag_start) else {
+                break;
+            };
+
+            let name_end = xml[tag_start + 1..tag_end]
+                .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+                .map_or(tag_end, |n| tag_start + 1 + n);
+
+            let is_self_closing = bytes[tag_end - 2] == b'/';
+
+            if !is_self_closing {
+                stack.push((local_name(&xml[tag_start + 1..name_end]), tag_end));
+            }
+
+            Some(tag_end)
+        };
+
+        match tag_end {
+            Some(tag_end) => i = tag_end,
+            None => break,
+        }
+    }
+
+    ranges
+}
+
+/// Find the byte range of the text of the first element at the specified path of element names.
+pub fn find_xml_element(xml: &str, path: &[&str]) -> Option<Range<usize>> {
+    find_xml_elements(xml, path).into_iter().next()
+}
+
+/// Get the unescaped text of the first element at the specified path of element names.
+pub fn get_xml_text<'a>(xml: &'a str, path: &[&str]) -> Option<Cow<'a, str>> {
+    find_xml_element(xml, path).map(|range| unescape_xml(&xml[range]))
+}
+
+/// Replace the text of the first element at the specified path of element names,
+/// leaving the rest of the document untouched.
+/// Returns None if no such element exists.
+pub fn set_xml_text(xml: &str, path: &[&str], value: &str) -> Option<String> {
+    let range = find_xml_element(xml, path)?;
+
+    Some(replace_range(xml, range, &escape_xml(value)))
+}
+
+/// Replace the specified range of a document.
+pub fn replace_range(text: &str, range: Range<usize>, value: &str) -> String {
+    let mut new_text = String::with_capacity(text.len() + value.len());
+    new_text.push_str(&text[..range.start]);
+    new_text.push_str(value);
+    new_text.push_str(&text[range.end..]);
+
+    new_text
+}
+
+/// Escape text for use as XML element content.
+pub fn escape_xml(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>']) {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;"))
+}
+
+/// Unescape the text content of an element, replacing predefined entities and character references
+/// such as `&#45;` or `&#x2D;`, and unwrapping CDATA sections, whose content is kept as is.
+/// Unknown entities are left unchanged.
+pub fn unescape_xml(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') && !text.contains("<![CDATA[") {
+        return Cow::Borrowed(text);
+    }
+
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(n) = rest.find(['&', '<']) {
+        unescaped.push_str(&rest[..n]);
+        rest = &rest[n..];
+
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+
+            unescaped.push_str(&cdata[..end]);
+            rest = cdata.get(end + 3..).unwrap_or_default();
+            continue;
+        }
+
+        let reference = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "amp" => '&',
+                name => {
+                    let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => name.strip_prefix('#')?.parse().ok()?,
+                    };
+
+                    char::from_u32(code)?
+                }
+            };
+
+            Some((c, end + 1))
+        });
+
+        match reference {
+            Some((c, len)) => {
+                unescaped.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                unescaped.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+
+    Cow::Owned(unescaped)
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn trim_range(text: &str, range: Range<usize>) -> Range<usize> {
+    let s = &text[range.clone()];
+
+    let start = range.start + (s.len() - s.trim_start().len());
+    let end = start + s.trim().len();
+
+    start..end
+}
+
+/// Find the position after the specified terminator, starting at the specified position.
+fn find_end(text: &str, i: usize, terminator: &str) -> Option<usize> {
+    text[i..].find(terminator).map(|n| i + n + terminator.len())
+}
+
+/// Skip a start tag, returning the position after its closing bracket.
+/// Brackets inside quoted attribute values are ignored.
+fn skip_start_tag(bytes: &[u8], mut i: usize) -> Option<usize> {
+    let mut quote = None;
+
+    while i < bytes.len() {
+        match (quote, bytes[i]) {
+            (None, b'"' | b'\'') => quote = Some(bytes[i]),
+            (Some(q), c) if c == q => quote = None,
+            (None, b'>') => return Some(i + 1),
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+/// Skip a declaration such as DOCTYPE, including any internal subset, returning the position after it.
+fn skip_declaration(bytes: &[u8], mut i: usize) -> Option<usize> {
+    let mut depth = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'[' => depth += 1,
+            b']' => depth -= 1,
+            b'>' if depth == 0 => return Some(i + 1),
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const XML: &str = "<?xml version=\"1.0\"?>\r\n<!-- <version>0.0.0</version> -->\r\n<project xmlns=\"urn:x\">\r\n  <parent a=\"/>\">\r\n    <version>1.0.0</version>\r\n  </parent>\r\n  <empty/>\r\n  <x:version>\r\n    2.0.0-SNAPSHOT\r\n  </x:version>\r\n  <name><![CDATA[<version>]]> &amp; co</name>\r\n</project>\r\n";
+
+    #[test]
+    /// Test finding element text at a path
+    fn test_find_xml_element() {
+        let range = find_xml_element(XML, &["project", "version"]).unwrap();
+        assert_eq!(&XML[range], "2.0.0-SNAPSHOT");
+
+        let range = find_xml_element(XML, &["project", "parent", "version"]).unwrap();
+        assert_eq!(&XML[range], "1.0.0");
+
+        assert_eq!(find_xml_element(XML, &["project", "empty"]), None);
+        assert_eq!(find_xml_element(XML, &["version"]), None);
+        assert_eq!(
+            get_xml_text(XML, &["project", "name"]).as_deref(),
+            Some("<version> & co")
+        );
+    }
+
+    #[test]
+    /// Test unescaping entities, character references and CDATA sections
+    fn test_unescape_xml() {
+        assert_eq!(unescape_xml("1.0.0"), "1.0.0");
+        assert_eq!(unescape_xml("1.0.0&#45;beta&#x2E;1"), "1.0.0-beta.1");
+        assert_eq!(unescape_xml("&lt;&amp;lt;&gt;"), "<&lt;>");
+        assert_eq!(unescape_xml("<![CDATA[&amp;]]>&amp;"), "&amp;&");
+        assert_eq!(unescape_xml("a & b &unknown; &#xZZ;"), "a & b &unknown; &#xZZ;");
+    }
+
+    #[test]
+    /// Test that replacing element text leaves the rest of the document untouched
+    fn test_set_xml_text() {
+        assert_eq!(
+            set_xml_text(XML, &["project", "version"], "2.1.0").unwrap(),
+            XML.replace("2.0.0-SNAPSHOT", "2.1.0")
+        );
+        assert_eq!(set_xml_text(XML, &["project", "missing"], "2.1.0"), None);
+    }
+}
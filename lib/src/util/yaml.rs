@@ -0,0 +1,187 @@
+//! Format-preserving editing of top-level values in YAML documents.
+//!
+//! Only simple `key: value` pairs at the top level of a document are supported, which is enough
+//! for manifests such as Chart.yaml. Values are located by their exact span in the original text,
+//! so that they can be replaced without affecting comments, formatting or the rest of the document,
+//! and the quote style of each value is kept.
+
+use std::ops::Range;
+
+/// A scalar value in a YAML document.
+#[derive(Debug, Eq, PartialEq)]
+struct Scalar {
+    /// Range of the value, excluding any quotes.
+    range: Range<usize>,
+    /// Quote character, if the value is quoted.
+    quote: Option<char>,
+}
+
+/// Get the unescaped value of a top-level key.
+/// Returns None if the key does not exist.
+pub fn get_yaml_string(text: &str, key: &str) -> Option<String> {
+    find_top_level_scalar(text, key).map(|scalar| scalar_value(text, &scalar))
+}
+
+/// Set the value of a top-level key, keeping its quote style.
+/// If the key does not exist, it is added at the end of the document.
+pub fn set_yaml_string(text: &str, key: &str, value: &str) -> String {
+    let Some(scalar) = find_top_level_scalar(text, key) else {
+        let newline = if text.contains("\r\n") { "\r\n" } else { "\n" };
+        let separator = if text.is_empty() || text.ends_with('\n') {
+            ""
+        } else {
+            newline
+        };
+
+        return format!("{}{}{}: {}{}", text, separator, key, quote_plain(value), newline);
+    };
+
+    let value = match scalar.quote {
+        Some('"') => value.replace('\\', "\\\\").replace('"', "\\\""),
+        Some('\'') => value.replace('\'', "''"),
+        _ => quote_plain(value),
+    };
+
+    format!("{}{}{}", &text[..scalar.range.start], value, &text[scalar.range.end..])
+}
+
+/// Find the value of a top-level key in a YAML document.
+fn find_top_level_scalar(text: &str, key: &str) -> Option<Scalar> {
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let Some(rest) = line.strip_prefix(key) else {
+            continue;
+        };
+
+        let Some(rest) = rest.trim_start_matches([' ', '\t']).strip_prefix(':') else {
+            continue;
+        };
+
+        let value = rest.trim_start_matches([' ', '\t']);
+        let value_start = line_start + (line.len() - value.len());
+
+        let scalar = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let end = find_closing_quote(value, quote)?;
+
+                Scalar {
+                    range: value_start + 1..value_start + end,
+                    quote: Some(quote),
+                }
+            }
+            _ => {
+                // A plain value ends at a comment or the end of the line
+                let end = value.find(" #").unwrap_or(value.len());
+                let plain = value[..end].trim_end();
+
+                Scalar {
+                    range: value_start..value_start + plain.len(),
+                    quote: None,
+                }
+            }
+        };
+
+        return Some(scalar);
+    }
+
+    None
+}
+
+/// Find the position of the closing quote of a quoted value starting at the beginning of the text.
+fn find_closing_quote(text: &str, quote: char) -> Option<usize> {
+    let mut chars = text.char_indices().skip(1).peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if quote == '"' => {
+                chars.next();
+            }
+            // Single quotes are escaped by doubling them
+            '\'' if quote == '\'' && chars.peek().is_some_and(|(_, c)| *c == '\'') => {
+                chars.next();
+            }
+            c if c == quote => return Some(i),
+            '\r' | '\n' => return None,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Get the unescaped value of a scalar.
+fn scalar_value(text: &str, scalar: &Scalar) -> String {
+    let raw = &text[scalar.range.clone()];
+
+    match scalar.quote {
+        Some('"') => raw.replace("\\\"", "\"").replace("\\\\", "\\"),
+        Some('\'') => raw.replace("''", "'"),
+        _ => raw.to_owned(),
+    }
+}
+
+/// Quote a value if it would not be read back as the same string when written as a plain scalar,
+/// such as versions like `1.10`, which would be read as a number.
+fn quote_plain(value: &str) -> String {
+    let is_number = value.parse::<f64>().is_ok();
+    let is_special = matches!(
+        value.to_ascii_lowercase().as_str(),
+        "" | "~" | "null" | "true" | "false" | "yes" | "no" | "on" | "off"
+    );
+    let has_indicator = value.starts_with(|c: char| "-?:,[]{}#&*!|>'\"%@`".contains(c))
+        || value.contains(": ")
+        || value.contains(" #")
+        || value != value.trim();
+
+    if is_number || is_special || has_indicator {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CHART: &str = "apiVersion: v2\r\nname: app\r\n# version: 0.0.1\r\nversion: 1.2.0 # chart version\r\nappVersion: '1.10'\r\ndependencies:\r\n  - name: db\r\n    version: 3.0.0\r\n";
+
+    #[test]
+    /// Test finding and reading top-level values
+    fn test_find_top_level_scalar() {
+        let scalar = find_top_level_scalar(CHART, "version").unwrap();
+        assert_eq!(scalar_value(CHART, &scalar), "1.2.0");
+        assert_eq!(scalar.quote, None);
+
+        let scalar = find_top_level_scalar(CHART, "appVersion").unwrap();
+        assert_eq!(scalar_value(CHART, &scalar), "1.10");
+        assert_eq!(scalar.quote, Some('\''));
+
+        assert_eq!(find_top_level_scalar(CHART, "kubeVersion"), None);
+    }
+
+    #[test]
+    /// Test that setting values keeps the quote style and the rest of the document
+    fn test_set_yaml_string() {
+        assert_eq!(
+            set_yaml_string(CHART, "version", "1.3.0-rc.1"),
+            CHART.replace("version: 1.2.0 #", "version: 1.3.0-rc.1 #")
+        );
+        assert_eq!(
+            set_yaml_string(CHART, "appVersion", "it's"),
+            CHART.replace("'1.10'", "'it''s'")
+        );
+        assert_eq!(
+            set_yaml_string("name: app\nappVersion: 1.0.0\n", "appVersion", "1.2"),
+            "name: app\nappVersion: \"1.2\"\n"
+        );
+        assert_eq!(
+            set_yaml_string("name: app", "appVersion", "1.2.0"),
+            "name: app\nappVersion: 1.2.0\n"
+        );
+    }
+}
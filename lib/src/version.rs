@@ -1,38 +1,176 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::{str::FromStr, sync::LazyLock};
 
 use regex::Regex;
+use thiserror::Error;
 
-static REGEX_PARSE_SEMVER: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r#"^(\d+)\.(\d+)\.(\d+)(?:-([\w\d\-\.]+))?(?:\+([\w\d\-\.]+))?"#).unwrap());
 static REGEX_SPLIT_NUMBERED_PRERELEASE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"([\w\-\.]*?)(\d+)"#).unwrap());
 
 /// Structure representing a SemVer compliant version.
+///
+/// As an extension to SemVer, an optional fourth numeric component (revision) is supported,
+/// for compatibility with .NET and Windows versions. It is only accepted when parsing
+/// if enabled in the [`ParseMode`].
+///
+/// Equality, ordering and hashing follow SemVer 2.0 precedence rules,
+/// meaning that build metadata is ignored. A missing revision is equivalent to 0.
 #[derive(Clone, Debug)]
 pub struct Version {
     pub major: u32,
     pub minor: u32,
     pub patch: u32,
+    pub revision: Option<u32>,
     pub prerelease: Option<String>,
     pub build: Option<String>,
 }
 
+/// How strictly version strings should be parsed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ParseMode {
+    /// Additionally accept surrounding whitespace, a leading 'v', leading zeros,
+    /// underscores and empty identifiers, as found in some legacy version files.
+    pub lenient: bool,
+    /// Accept an optional fourth numeric component (revision), e.g. 1.2.3.4.
+    /// Versions with a revision are not valid SemVer.
+    pub revision: bool,
+}
+
+impl ParseMode {
+    /// Only accept versions that are fully compliant with SemVer 2.0.
+    pub const STRICT: Self = Self {
+        lenient: false,
+        revision: false,
+    };
+
+    /// Accept legacy version strings in addition to SemVer 2.0.
+    pub const LENIENT: Self = Self {
+        lenient: true,
+        revision: false,
+    };
+
+    /// Get the same parse mode, with the revision component enabled or disabled.
+    pub const fn with_revision(self, revision: bool) -> Self {
+        Self { revision, ..self }
+    }
+}
+
+/// A component of a version string.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VersionComponent {
+    Major,
+    Minor,
+    Patch,
+    Revision,
+    Prerelease,
+    Build,
+}
+
+impl fmt::Display for VersionComponent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Major => "major version",
+            Self::Minor => "minor version",
+            Self::Patch => "patch version",
+            Self::Revision => "revision",
+            Self::Prerelease => "prerelease",
+            Self::Build => "build metadata",
+        })
+    }
+}
+
+/// Error returned when parsing an invalid version string.
+/// Positions are zero-based byte offsets into the string being parsed.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum VersionParseError {
+    #[error("version string is empty")]
+    Empty,
+    #[error("missing {component} at position {position}")]
+    MissingComponent {
+        component: VersionComponent,
+        position: usize,
+    },
+    #[error("{component} at position {position} has a leading zero")]
+    LeadingZero {
+        component: VersionComponent,
+        position: usize,
+    },
+    #[error("{component} at position {position} is too large")]
+    NumberTooLarge {
+        component: VersionComponent,
+        position: usize,
+    },
+    #[error("empty {component} identifier at position {position}")]
+    EmptyIdentifier {
+        component: VersionComponent,
+        position: usize,
+    },
+    #[error("invalid character '{character}' in {component} at position {position}")]
+    InvalidCharacter {
+        component: VersionComponent,
+        position: usize,
+        character: char,
+    },
+    #[error("unexpected revision at position {position}, as four-part versions are not enabled")]
+    UnexpectedRevision { position: usize },
+}
+
+/// Error returned when a bump cannot be performed on a version.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum BumpError {
+    #[error("version is not a prerelease")]
+    NotPrerelease,
+    #[error("prerelease '{0}' does not end in a number")]
+    UnnumberedPrerelease(String),
+    #[error("version has no build metadata")]
+    NoBuild,
+    #[error("build metadata '{0}' does not end in a number")]
+    UnnumberedBuild(String),
+    #[error("'{0}' is not a valid prerelease channel name")]
+    InvalidChannel(String),
+    #[error("unknown prerelease channel '{0}'")]
+    UnknownChannel(String),
+    #[error("'{0}' is the last prerelease channel")]
+    LastChannel(String),
+    #[error("version {0} is already a release")]
+    AlreadyRelease(String),
+    #[error("{option} cannot be used when bumping {bump}")]
+    NotApplicable { option: &'static str, bump: String },
+    #[error("calendar bumps require a CalVer version scheme to be configured")]
+    CalendarNotConfigured,
+    #[error("version is already up to date for the current date")]
+    CalendarUpToDate,
+    #[error("version date is later than the current date")]
+    CalendarInFuture,
+    #[error("cannot switch from prerelease channel '{from}' to '{to}', as that would lower the version")]
+    ChannelDowngrade { from: String, to: String },
+}
+
 impl Version {
     pub fn new(major: u32, minor: u32, patch: u32, prerelease: Option<&str>, build: Option<&str>) -> Self {
         Self {
             major,
             minor,
             patch,
+            revision: None,
             prerelease: prerelease.map(|s| s.to_owned()),
             build: build.map(|s| s.to_owned()),
         }
     }
 
+    /// Parse a version string using the specified parse mode.
+    pub fn parse(s: &str, mode: ParseMode) -> Result<Self, VersionParseError> {
+        VersionParser::new(s, mode).parse()
+    }
+
     /// Bump the major version.
     pub fn bump_major(&self) -> Self {
         Self {
             major: self.major + 1,
             minor: 0,
             patch: 0,
+            revision: self.revision.map(|_| 0),
             prerelease: None,
             build: self.build.clone(),
         }
@@ -44,6 +182,7 @@ impl Version {
             major: self.major,
             minor: self.minor + 1,
             patch: 0,
+            revision: self.revision.map(|_| 0),
             prerelease: None,
             build: self.build.clone(),
         }
@@ -55,65 +194,200 @@ impl Version {
             major: self.major,
             minor: self.minor,
             patch: self.patch + 1,
+            revision: self.revision.map(|_| 0),
             prerelease: None,
             build: self.build.clone(),
         }
     }
 
-    /// Bump the number of a numbered prerelease string.
-    pub fn bump_prerelease(&self) -> Self {
-        if let Some(prerelease) = &self.prerelease {
-            if let Some((prefix, number)) = split_numbered_prerelease(prerelease) {
-                Self {
-                    major: self.major,
-                    minor: self.minor,
-                    patch: self.patch,
-                    prerelease: Some(format!("{}{}", prefix, number + 1)),
-                    build: self.build.clone(),
-                }
-            } else {
-                self.clone()
-            }
-        } else {
-            self.clone()
+    /// Bump the revision, adding it if it is not present.
+    pub fn bump_revision(&self) -> Self {
+        Self {
+            major: self.major,
+            minor: self.minor,
+            patch: self.patch,
+            revision: Some(self.revision.unwrap_or(0) + 1),
+            prerelease: None,
+            build: self.build.clone(),
         }
     }
 
+    /// Bump the number of a numbered prerelease string.
+    pub fn bump_prerelease(&self) -> Result<Self, BumpError> {
+        let prerelease = self.prerelease.as_ref().ok_or(BumpError::NotPrerelease)?;
+        let (prefix, number) =
+            split_numbered_prerelease(prerelease).ok_or_else(|| BumpError::UnnumberedPrerelease(prerelease.clone()))?;
+
+        Ok(Self {
+            major: self.major,
+            minor: self.minor,
+            patch: self.patch,
+            revision: self.revision,
+            prerelease: Some(format!("{}{}", prefix, number + 1)),
+            build: self.build.clone(),
+        })
+    }
+
     /// Bump the number of a numbered build string.
-    pub fn bump_build(&self) -> Self {
-        if let Some(build) = &self.build {
-            if let Some((prefix, number)) = split_numbered_prerelease(build) {
-                Self {
-                    major: self.major,
-                    minor: self.minor,
-                    patch: self.patch,
-                    prerelease: self.prerelease.to_owned(),
-                    build: Some(format!("{}{}", prefix, number + 1)),
-                }
-            } else {
-                self.clone()
-            }
-        } else {
-            self.clone()
+    pub fn bump_build(&self) -> Result<Self, BumpError> {
+        let build = self.build.as_ref().ok_or(BumpError::NoBuild)?;
+        let (prefix, number) =
+            split_numbered_prerelease(build).ok_or_else(|| BumpError::UnnumberedBuild(build.clone()))?;
+
+        Ok(Self {
+            major: self.major,
+            minor: self.minor,
+            patch: self.patch,
+            revision: self.revision,
+            prerelease: self.prerelease.to_owned(),
+            build: Some(format!("{}{}", prefix, number + 1)),
+        })
+    }
+
+    /// Finalize a prerelease, turning it into a release, optionally also removing build metadata.
+    pub fn release(&self, strip_build: bool) -> Result<Self, BumpError> {
+        if self.prerelease.is_none() {
+            return Err(BumpError::AlreadyRelease(self.to_string()));
         }
+
+        Ok(Self {
+            major: self.major,
+            minor: self.minor,
+            patch: self.patch,
+            revision: self.revision,
+            prerelease: None,
+            build: if strip_build { None } else { self.build.clone() },
+        })
+    }
+
+    /// Start the first prerelease of the specified channel, e.g. "beta.1".
+    pub fn start_prerelease(&self, channel: &str, channels: &[String]) -> Result<Self, BumpError> {
+        validate_channel(channel, channels)?;
+
+        Ok(Self {
+            prerelease: Some(format!("{}.1", channel)),
+            ..self.clone()
+        })
+    }
+
+    /// Bump the prerelease within the specified channel.
+    /// If the version is currently in a different channel, switch to the first prerelease of the new channel.
+    /// Switching to a channel with lower precedence than the current one is not allowed.
+    pub fn bump_prerelease_channel(&self, channel: &str, channels: &[String]) -> Result<Self, BumpError> {
+        let current_channel = self.prerelease_channel().ok_or(BumpError::NotPrerelease)?;
+
+        if current_channel == channel {
+            return self.bump_prerelease();
+        }
+
+        let version = self.start_prerelease(channel, channels)?;
+
+        // If channels are configured, their order decides. Otherwise, fall back to SemVer precedence.
+        let is_downgrade = match (
+            channels.iter().position(|c| c == current_channel),
+            channels.iter().position(|c| c == channel),
+        ) {
+            (Some(current_index), Some(index)) => index < current_index,
+            _ => version < *self,
+        };
+
+        if is_downgrade {
+            return Err(BumpError::ChannelDowngrade {
+                from: current_channel.to_owned(),
+                to: channel.to_owned(),
+            });
+        }
+
+        Ok(version)
+    }
+
+    /// Promote the prerelease to the first prerelease of the next channel in the list.
+    pub fn promote_prerelease_channel(&self, channels: &[String]) -> Result<Self, BumpError> {
+        let current_channel = self.prerelease_channel().ok_or(BumpError::NotPrerelease)?;
+
+        let index = channels
+            .iter()
+            .position(|c| c == current_channel)
+            .ok_or_else(|| BumpError::UnknownChannel(current_channel.to_owned()))?;
+
+        let next_channel = channels
+            .get(index + 1)
+            .ok_or_else(|| BumpError::LastChannel(current_channel.to_owned()))?;
+
+        self.start_prerelease(next_channel, channels)
+    }
+
+    /// Get the channel of the prerelease, if any.
+    /// This is the prerelease with any trailing number and separator removed, e.g. "beta" for "beta.2".
+    pub fn prerelease_channel(&self) -> Option<&str> {
+        let prerelease = self.prerelease.as_deref()?;
+
+        let channel = match split_numbered_prerelease(prerelease) {
+            Some((prefix, _)) => prefix.trim_end_matches(['.', '-']),
+            None => prerelease,
+        };
+
+        Some(channel)
     }
 }
 
 impl FromStr for Version {
-    type Err = String;
+    type Err = VersionParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cap = REGEX_PARSE_SEMVER
-            .captures(s)
-            .ok_or_else(|| format!("Invalid version string: {}", s))?;
+        Self::parse(s, ParseMode::STRICT)
+    }
+}
 
-        Ok(Self {
-            major: cap[1].parse().unwrap(),
-            minor: cap[2].parse().unwrap(),
-            patch: cap[3].parse().unwrap(),
-            prerelease: cap.get(4).map(|m| m.as_str().to_owned()),
-            build: cap.get(5).map(|m| m.as_str().to_owned()),
-        })
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then_with(|| self.minor.cmp(&other.minor))
+            .then_with(|| self.patch.cmp(&other.patch))
+            .then_with(|| self.revision.unwrap_or(0).cmp(&other.revision.unwrap_or(0)))
+            .then_with(|| compare_prerelease(self.prerelease.as_deref(), other.prerelease.as_deref()))
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Build metadata is deliberately left out, to stay consistent with Eq.
+        self.major.hash(state);
+        self.minor.hash(state);
+        self.patch.hash(state);
+        self.revision.unwrap_or(0).hash(state);
+        self.prerelease.hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Version {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Version {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -121,10 +395,11 @@ impl std::fmt::Display for Version {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}.{}.{}{}{}",
+            "{}.{}.{}{}{}{}",
             self.major,
             self.minor,
             self.patch,
+            self.revision.map_or_else(|| "".to_owned(), |r| format!(".{}", r)),
             self.prerelease
                 .as_ref()
                 .map_or_else(|| "".to_owned(), |p| format!("-{}", p)),
@@ -133,6 +408,293 @@ impl std::fmt::Display for Version {
     }
 }
 
+/// Check that a prerelease channel is a valid alphanumeric SemVer identifier,
+/// and that it is one of the configured channels, if any are configured.
+fn validate_channel(channel: &str, channels: &[String]) -> Result<(), BumpError> {
+    if channel.is_empty()
+        || channel.bytes().all(|b| b.is_ascii_digit())
+        || !channel.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    {
+        return Err(BumpError::InvalidChannel(channel.to_owned()));
+    }
+
+    if !channels.is_empty() && !channels.iter().any(|c| c == channel) {
+        return Err(BumpError::UnknownChannel(channel.to_owned()));
+    }
+
+    Ok(())
+}
+
+/// Parser for version strings, keeping track of the current position for error reporting.
+struct VersionParser<'a> {
+    input: &'a str,
+    position: usize,
+    mode: ParseMode,
+}
+
+impl<'a> VersionParser<'a> {
+    fn new(input: &'a str, mode: ParseMode) -> Self {
+        Self {
+            input,
+            position: 0,
+            mode,
+        }
+    }
+
+    fn parse(mut self) -> Result<Version, VersionParseError> {
+        if self.mode.lenient {
+            // Skip leading whitespace and an optional 'v' prefix.
+            let trimmed = self.input.trim_start();
+            self.position = self.input.len() - trimmed.len();
+
+            if trimmed.starts_with(['v', 'V']) {
+                self.position += 1;
+            }
+
+            // Ignore trailing whitespace.
+            self.input = self.input.trim_end();
+        }
+
+        if self.position >= self.input.len() {
+            return Err(VersionParseError::Empty);
+        }
+
+        let major = self.parse_number(VersionComponent::Major)?;
+        self.expect_separator(VersionComponent::Major, VersionComponent::Minor)?;
+        let minor = self.parse_number(VersionComponent::Minor)?;
+        self.expect_separator(VersionComponent::Minor, VersionComponent::Patch)?;
+        let patch = self.parse_number(VersionComponent::Patch)?;
+
+        let revision = if self.peek() == Some('.') {
+            if !self.mode.revision {
+                return Err(VersionParseError::UnexpectedRevision {
+                    position: self.position,
+                });
+            }
+
+            self.position += 1;
+            Some(self.parse_number(VersionComponent::Revision)?)
+        } else {
+            None
+        };
+
+        let prerelease = if self.peek() == Some('-') {
+            self.position += 1;
+            Some(self.parse_identifiers(VersionComponent::Prerelease)?)
+        } else {
+            None
+        };
+
+        let build = if self.peek() == Some('+') {
+            self.position += 1;
+            Some(self.parse_identifiers(VersionComponent::Build)?)
+        } else {
+            None
+        };
+
+        // Anything left over at this point is invalid.
+        if let Some(character) = self.peek() {
+            let component = if build.is_some() {
+                VersionComponent::Build
+            } else if prerelease.is_some() {
+                VersionComponent::Prerelease
+            } else if revision.is_some() {
+                VersionComponent::Revision
+            } else {
+                VersionComponent::Patch
+            };
+
+            return Err(VersionParseError::InvalidCharacter {
+                component,
+                position: self.position,
+                character,
+            });
+        }
+
+        Ok(Version {
+            major,
+            minor,
+            patch,
+            revision,
+            prerelease: prerelease.map(|s| s.to_owned()),
+            build: build.map(|s| s.to_owned()),
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    /// Consume characters for as long as the predicate returns true, returning the consumed string.
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let start = self.position;
+
+        while let Some(c) = self.peek() {
+            if !predicate(c) {
+                break;
+            }
+
+            self.position += c.len_utf8();
+        }
+
+        &self.input[start..self.position]
+    }
+
+    fn parse_number(&mut self, component: VersionComponent) -> Result<u32, VersionParseError> {
+        let position = self.position;
+        let digits = self.take_while(|c| c.is_ascii_digit());
+
+        if digits.is_empty() {
+            return Err(match self.peek() {
+                Some(character) => VersionParseError::InvalidCharacter {
+                    component,
+                    position,
+                    character,
+                },
+                None => VersionParseError::MissingComponent { component, position },
+            });
+        }
+
+        if !self.mode.lenient && has_leading_zero(digits) {
+            return Err(VersionParseError::LeadingZero { component, position });
+        }
+
+        digits
+            .parse()
+            .map_err(|_| VersionParseError::NumberTooLarge { component, position })
+    }
+
+    fn expect_separator(
+        &mut self,
+        component: VersionComponent,
+        next_component: VersionComponent,
+    ) -> Result<(), VersionParseError> {
+        match self.peek() {
+            Some('.') => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(character) => Err(VersionParseError::InvalidCharacter {
+                component,
+                position: self.position,
+                character,
+            }),
+            None => Err(VersionParseError::MissingComponent {
+                component: next_component,
+                position: self.position,
+            }),
+        }
+    }
+
+    /// Parse a dot-separated series of prerelease or build identifiers.
+    fn parse_identifiers(&mut self, component: VersionComponent) -> Result<&'a str, VersionParseError> {
+        let start = self.position;
+        let lenient = self.mode.lenient;
+
+        loop {
+            let position = self.position;
+            let identifier = self.take_while(|c| c.is_ascii_alphanumeric() || c == '-' || (lenient && c == '_'));
+
+            if !lenient {
+                if identifier.is_empty() {
+                    return Err(match self.peek() {
+                        Some(character) if character != '.' => VersionParseError::InvalidCharacter {
+                            component,
+                            position,
+                            character,
+                        },
+                        _ => VersionParseError::EmptyIdentifier { component, position },
+                    });
+                }
+
+                // Numeric prerelease identifiers must not have leading zeros.
+                // Build metadata identifiers are exempt from this rule.
+                if component == VersionComponent::Prerelease
+                    && identifier.bytes().all(|b| b.is_ascii_digit())
+                    && has_leading_zero(identifier)
+                {
+                    return Err(VersionParseError::LeadingZero { component, position });
+                }
+            }
+
+            if self.peek() == Some('.') {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+
+        let identifiers = &self.input[start..self.position];
+
+        if identifiers.is_empty() {
+            return Err(VersionParseError::EmptyIdentifier {
+                component,
+                position: start,
+            });
+        }
+
+        Ok(identifiers)
+    }
+}
+
+fn has_leading_zero(digits: &str) -> bool {
+    digits.len() > 1 && digits.starts_with('0')
+}
+
+/// Compare two optional prerelease strings according to SemVer precedence rules.
+/// A version without a prerelease has higher precedence than one with a prerelease.
+pub(crate) fn compare_prerelease(a: Option<&str>, b: Option<&str>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => {
+            let mut a_identifiers = a.split('.');
+            let mut b_identifiers = b.split('.');
+
+            loop {
+                match (a_identifiers.next(), b_identifiers.next()) {
+                    (None, None) => return Ordering::Equal,
+                    // A larger set of identifiers has higher precedence if all preceding identifiers are equal.
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(_), None) => return Ordering::Greater,
+                    (Some(a), Some(b)) => match compare_prerelease_identifier(a, b) {
+                        Ordering::Equal => {}
+                        ordering => return ordering,
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// Compare two individual prerelease identifiers.
+/// Numeric identifiers are compared numerically and always have lower precedence than alphanumeric ones,
+/// which are compared lexically in ASCII sort order.
+fn compare_prerelease_identifier(a: &str, b: &str) -> Ordering {
+    fn is_numeric(s: &str) -> bool {
+        !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+    }
+
+    match (is_numeric(a), is_numeric(b)) {
+        (true, true) => {
+            // Compare by length first, so that arbitrarily large numbers can be compared without overflowing.
+            let a_trimmed = a.trim_start_matches('0');
+            let b_trimmed = b.trim_start_matches('0');
+
+            a_trimmed
+                .len()
+                .cmp(&b_trimmed.len())
+                .then_with(|| a_trimmed.cmp(b_trimmed))
+                // Fall back to comparing the raw strings to keep ordering consistent with hashing.
+                .then_with(|| a.cmp(b))
+        }
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.cmp(b),
+    }
+}
+
 /// Split a prerelease string with a number at the end into separate string prefix and number components.
 pub fn split_numbered_prerelease(s: &str) -> Option<(&str, u32)> {
     match REGEX_SPLIT_NUMBERED_PRERELEASE.captures(s) {
@@ -150,6 +712,12 @@ pub fn split_numbered_prerelease(s: &str) -> Option<(&str, u32)> {
 mod test {
     use super::*;
 
+    const FOUR_PART: ParseMode = ParseMode::STRICT.with_revision(true);
+
+    fn parse_four_part(s: &str) -> Result<Version, VersionParseError> {
+        Version::parse(s, FOUR_PART)
+    }
+
     #[test]
     /// Test the split_numbered_prerelease utility function
     fn test_split_numbered_prerelease() {
@@ -175,6 +743,183 @@ mod test {
         assert_eq!("1.2.3-beta.6".parse::<Version>().unwrap().to_string(), "1.2.3-beta.6");
         assert_eq!("1.2.3".parse::<Version>().unwrap().to_string(), "1.2.3");
         assert_eq!("1.2.3+build.9".parse::<Version>().unwrap().to_string(), "1.2.3+build.9");
+        assert_eq!(
+            parse_four_part("1.2.3.4-beta.6+build.9").unwrap().to_string(),
+            "1.2.3.4-beta.6+build.9"
+        );
+    }
+
+    #[test]
+    /// Test that invalid version strings are rejected with the correct error
+    fn test_parse_errors() {
+        use VersionComponent::*;
+
+        assert_eq!("".parse::<Version>().unwrap_err(), VersionParseError::Empty);
+        assert_eq!(
+            "1.2".parse::<Version>().unwrap_err(),
+            VersionParseError::MissingComponent {
+                component: Patch,
+                position: 3
+            }
+        );
+        assert_eq!(
+            "1.2.3garbage".parse::<Version>().unwrap_err(),
+            VersionParseError::InvalidCharacter {
+                component: Patch,
+                position: 5,
+                character: 'g'
+            }
+        );
+        assert_eq!(
+            "1.02.3".parse::<Version>().unwrap_err(),
+            VersionParseError::LeadingZero {
+                component: Minor,
+                position: 2
+            }
+        );
+        assert_eq!(
+            "1.2.3-beta.01".parse::<Version>().unwrap_err(),
+            VersionParseError::LeadingZero {
+                component: Prerelease,
+                position: 11
+            }
+        );
+        assert_eq!(
+            "1.2.3-beta..1".parse::<Version>().unwrap_err(),
+            VersionParseError::EmptyIdentifier {
+                component: Prerelease,
+                position: 11
+            }
+        );
+        assert_eq!(
+            "1.2.3+".parse::<Version>().unwrap_err(),
+            VersionParseError::EmptyIdentifier {
+                component: Build,
+                position: 6
+            }
+        );
+        assert_eq!(
+            "1.2.3-beta_1".parse::<Version>().unwrap_err(),
+            VersionParseError::InvalidCharacter {
+                component: Prerelease,
+                position: 10,
+                character: '_'
+            }
+        );
+        assert_eq!(
+            "99999999999.0.0".parse::<Version>().unwrap_err(),
+            VersionParseError::NumberTooLarge {
+                component: Major,
+                position: 0
+            }
+        );
+
+        // Strict SemVer does not allow a revision, unless four-part versions are enabled
+        assert_eq!(
+            "1.2.3.4".parse::<Version>().unwrap_err(),
+            VersionParseError::UnexpectedRevision { position: 5 }
+        );
+        assert_eq!(
+            Version::parse("1.2.3.4", ParseMode::LENIENT).unwrap_err(),
+            VersionParseError::UnexpectedRevision { position: 5 }
+        );
+        assert_eq!(
+            parse_four_part("1.2.3.").unwrap_err(),
+            VersionParseError::MissingComponent {
+                component: Revision,
+                position: 6
+            }
+        );
+        assert_eq!(
+            parse_four_part("1.2.3.4.5").unwrap_err(),
+            VersionParseError::InvalidCharacter {
+                component: Revision,
+                position: 7,
+                character: '.'
+            }
+        );
+
+        // Leading zeros are allowed in build metadata
+        assert_eq!(
+            "1.2.3+build.007".parse::<Version>().unwrap().to_string(),
+            "1.2.3+build.007"
+        );
+    }
+
+    #[test]
+    /// Test lenient parsing of legacy version strings
+    fn test_parse_lenient() {
+        assert_eq!(
+            Version::parse(" v01.2.3-beta_1..2\n", ParseMode::LENIENT)
+                .unwrap()
+                .to_string(),
+            "1.2.3-beta_1..2"
+        );
+
+        // Trailing garbage is rejected even in lenient mode
+        assert_eq!(
+            Version::parse("1.2.3garbage", ParseMode::LENIENT).unwrap_err(),
+            VersionParseError::InvalidCharacter {
+                component: VersionComponent::Patch,
+                position: 5,
+                character: 'g'
+            }
+        );
+    }
+
+    #[test]
+    /// Test SemVer precedence ordering
+    fn test_precedence() {
+        // Example sequence from the SemVer 2.0 specification
+        let versions: Vec<Version> = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.1.0",
+            "2.0.0",
+        ]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+
+        // A missing revision is equivalent to 0
+        assert_eq!("1.2.3".parse::<Version>().unwrap(), parse_four_part("1.2.3.0").unwrap());
+        assert!(parse_four_part("1.2.3.1").unwrap() > "1.2.3".parse::<Version>().unwrap());
+        assert!(parse_four_part("1.2.3.1").unwrap() < "1.2.4".parse::<Version>().unwrap());
+
+        // Build metadata does not affect precedence
+        assert_eq!(
+            "1.2.3+build.1".parse::<Version>().unwrap(),
+            "1.2.3+build.2".parse::<Version>().unwrap()
+        );
+        assert_eq!(
+            "1.2.3-beta.1+build.1"
+                .parse::<Version>()
+                .unwrap()
+                .cmp(&"1.2.3-beta.1".parse().unwrap()),
+            Ordering::Equal
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    /// Test that versions serialize to and from plain version strings
+    fn test_serde() {
+        let version: Version = serde_json::from_str(r#""1.2.3-beta.6+build.9""#).unwrap();
+
+        assert_eq!(version.to_string(), "1.2.3-beta.6+build.9");
+        assert_eq!(serde_json::to_string(&version).unwrap(), r#""1.2.3-beta.6+build.9""#);
+        assert!(serde_json::from_str::<Version>(r#""not a version""#).is_err());
     }
 
     #[test]
@@ -183,18 +928,139 @@ mod test {
         assert_eq!(Version::new(1, 2, 3, None, None).bump_major().to_string(), "2.0.0");
         assert_eq!(Version::new(1, 2, 3, None, None).bump_minor().to_string(), "1.3.0");
         assert_eq!(Version::new(1, 2, 3, None, None).bump_patch().to_string(), "1.2.4");
+        assert_eq!(Version::new(1, 2, 3, None, None).bump_revision().to_string(), "1.2.3.1");
+
+        let four_part = Version {
+            revision: Some(4),
+            ..Version::new(1, 2, 3, None, None)
+        };
+
+        assert_eq!(four_part.bump_minor().to_string(), "1.3.0.0");
+        assert_eq!(four_part.bump_revision().to_string(), "1.2.3.5");
 
         assert_eq!(
             Version::new(1, 2, 3, Some("beta.1"), None)
                 .bump_prerelease()
+                .unwrap()
                 .to_string(),
             "1.2.3-beta.2"
         );
         assert_eq!(
             Version::new(1, 2, 3, Some("beta.1"), Some("build.7"))
                 .bump_build()
+                .unwrap()
                 .to_string(),
             "1.2.3-beta.1+build.8"
         );
+
+        // No-op bumps are errors
+        assert_eq!(
+            Version::new(1, 2, 3, None, None).bump_prerelease().unwrap_err(),
+            BumpError::NotPrerelease
+        );
+        assert_eq!(
+            Version::new(1, 2, 3, Some("beta"), None).bump_prerelease().unwrap_err(),
+            BumpError::UnnumberedPrerelease("beta".to_owned())
+        );
+        assert_eq!(
+            Version::new(1, 2, 3, None, None).bump_build().unwrap_err(),
+            BumpError::NoBuild
+        );
+    }
+
+    #[test]
+    /// Test finalizing prereleases
+    fn test_release() {
+        assert_eq!(
+            Version::new(1, 4, 0, Some("rc.3"), Some("build.5"))
+                .release(false)
+                .unwrap()
+                .to_string(),
+            "1.4.0+build.5"
+        );
+        assert_eq!(
+            Version::new(1, 4, 0, Some("rc.3"), Some("build.5"))
+                .release(true)
+                .unwrap()
+                .to_string(),
+            "1.4.0"
+        );
+        assert_eq!(
+            Version::new(1, 4, 0, None, None).release(false).unwrap_err(),
+            BumpError::AlreadyRelease("1.4.0".to_owned())
+        );
+    }
+
+    #[test]
+    /// Test starting, switching and promoting prerelease channels
+    fn test_prerelease_channels() {
+        let channels: Vec<String> = vec!["alpha".to_owned(), "beta".to_owned(), "rc".to_owned()];
+
+        assert_eq!(
+            Version::new(1, 2, 3, None, None)
+                .bump_minor()
+                .start_prerelease("beta", &channels)
+                .unwrap()
+                .to_string(),
+            "1.3.0-beta.1"
+        );
+        assert_eq!(
+            Version::new(1, 2, 3, Some("alpha.4"), None)
+                .bump_prerelease_channel("beta", &channels)
+                .unwrap()
+                .to_string(),
+            "1.2.3-beta.1"
+        );
+        assert_eq!(
+            Version::new(1, 2, 3, Some("beta.1"), None)
+                .bump_prerelease_channel("beta", &channels)
+                .unwrap()
+                .to_string(),
+            "1.2.3-beta.2"
+        );
+        assert_eq!(
+            Version::new(1, 2, 3, Some("beta.2"), None)
+                .promote_prerelease_channel(&channels)
+                .unwrap()
+                .to_string(),
+            "1.2.3-rc.1"
+        );
+
+        assert_eq!(
+            Version::new(1, 2, 3, Some("beta.2"), None)
+                .bump_prerelease_channel("alpha", &channels)
+                .unwrap_err(),
+            BumpError::ChannelDowngrade {
+                from: "beta".to_owned(),
+                to: "alpha".to_owned()
+            }
+        );
+        assert_eq!(
+            Version::new(1, 2, 3, Some("beta.2"), None)
+                .bump_prerelease_channel("alpha", &[])
+                .unwrap_err(),
+            BumpError::ChannelDowngrade {
+                from: "beta".to_owned(),
+                to: "alpha".to_owned()
+            }
+        );
+        assert_eq!(
+            Version::new(1, 2, 3, Some("rc.1"), None)
+                .promote_prerelease_channel(&channels)
+                .unwrap_err(),
+            BumpError::LastChannel("rc".to_owned())
+        );
+        assert_eq!(
+            Version::new(1, 2, 3, None, None)
+                .start_prerelease("preview", &channels)
+                .unwrap_err(),
+            BumpError::UnknownChannel("preview".to_owned())
+        );
+        assert_eq!(
+            Version::new(1, 2, 3, None, None)
+                .start_prerelease("beta.1", &channels)
+                .unwrap_err(),
+            BumpError::InvalidChannel("beta.1".to_owned())
+        );
     }
 }
@@ -0,0 +1,567 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::version::{self, Version};
+
+/// A version requirement, such as `^1.2`, `>=2.0.0-0, <3` or `~1.4.1 || ^2`.
+///
+/// Comparators within a set are separated by commas or whitespace, and must all match.
+/// Sets are separated by `||`, and at least one of them must match.
+///
+/// Supported operators are `=`, `>`, `>=`, `<`, `<=`, `~` (tilde) and `^` (caret),
+/// as well as wildcards (`*`, `1.x`, `1.2.*`). A version without an operator is treated as a caret requirement.
+///
+/// As in Cargo and npm, a prerelease version only matches if a comparator in the same set
+/// has a prerelease with the same major, minor and patch version.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VersionReq {
+    sets: Vec<Vec<Comparator>>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+    Wildcard,
+}
+
+/// A single comparator in a version requirement.
+/// Components that are not specified are treated as wildcards.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Comparator {
+    op: Op,
+    major: u32,
+    minor: Option<u32>,
+    patch: Option<u32>,
+    revision: Option<u32>,
+    prerelease: Option<String>,
+}
+
+/// Error returned when parsing an invalid version requirement.
+/// Positions are zero-based byte offsets into the string being parsed.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum VersionReqParseError {
+    #[error("version requirement is empty")]
+    Empty,
+    #[error("empty comparator set at position {0}")]
+    EmptySet(usize),
+    #[error("unexpected end of version requirement")]
+    UnexpectedEnd,
+    #[error("unexpected character '{character}' at position {position}")]
+    UnexpectedCharacter { position: usize, character: char },
+    #[error("number at position {0} has a leading zero")]
+    LeadingZero(usize),
+    #[error("number at position {0} is too large")]
+    NumberTooLarge(usize),
+    #[error("empty prerelease identifier at position {0}")]
+    EmptyIdentifier(usize),
+    #[error("prerelease at position {0} requires a patch version")]
+    PartialPrerelease(usize),
+}
+
+impl VersionReq {
+    /// Check whether a version matches this requirement.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.sets.iter().any(|set| matches_set(set, version))
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = VersionReqParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Err(VersionReqParseError::Empty);
+        }
+
+        let mut sets = Vec::new();
+        let mut offset = 0;
+
+        for set_str in s.split("||") {
+            sets.push(ReqParser::new(s, offset, offset + set_str.len()).parse_set()?);
+
+            offset += set_str.len() + 2;
+        }
+
+        Ok(Self { sets })
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, set) in self.sets.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" || ")?;
+            }
+
+            if set.is_empty() {
+                f.write_str("*")?;
+            }
+
+            for (j, comparator) in set.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(", ")?;
+                }
+
+                write!(f, "{}", comparator)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for VersionReq {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for VersionReq {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Comparator {
+    fn matches(&self, version: &Version) -> bool {
+        match self.op {
+            Op::Exact | Op::Wildcard => self.compare(version) == Ordering::Equal,
+            Op::Greater => self.compare(version) == Ordering::Greater,
+            Op::GreaterEq => self.compare(version) != Ordering::Less,
+            Op::Less => self.compare(version) == Ordering::Less,
+            Op::LessEq => self.compare(version) != Ordering::Greater,
+            Op::Tilde => {
+                self.compare(version) != Ordering::Less
+                    && version.major == self.major
+                    && self.minor.is_none_or(|minor| version.minor == minor)
+            }
+            Op::Caret => {
+                if self.compare(version) == Ordering::Less {
+                    return false;
+                }
+
+                // The first non-zero specified component must stay the same.
+                match (self.major, self.minor, self.patch) {
+                    (0, Some(0), Some(patch)) => version.major == 0 && version.minor == 0 && version.patch == patch,
+                    (0, Some(minor), _) => version.major == 0 && version.minor == minor,
+                    (major, _, _) => version.major == major,
+                }
+            }
+        }
+    }
+
+    /// Compare a version to the components specified in this comparator.
+    /// Unspecified components are ignored, so `1.2.5` is considered equal to `1.2`.
+    fn compare(&self, version: &Version) -> Ordering {
+        let (Some(minor), Some(patch)) = (self.minor, self.patch) else {
+            return version
+                .major
+                .cmp(&self.major)
+                .then_with(|| self.minor.map_or(Ordering::Equal, |minor| version.minor.cmp(&minor)));
+        };
+
+        let ordering = version
+            .major
+            .cmp(&self.major)
+            .then(version.minor.cmp(&minor))
+            .then(version.patch.cmp(&patch))
+            .then(
+                self.revision
+                    .map_or(Ordering::Equal, |revision| version.revision.unwrap_or(0).cmp(&revision)),
+            );
+
+        ordering.then_with(|| version::compare_prerelease(version.prerelease.as_deref(), self.prerelease.as_deref()))
+    }
+
+    /// Check whether this comparator allows prerelease versions of the specified version to match.
+    fn allows_prerelease_of(&self, version: &Version) -> bool {
+        self.prerelease.is_some()
+            && self.major == version.major
+            && self.minor == Some(version.minor)
+            && self.patch == Some(version.patch)
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.op {
+            Op::Exact => "=",
+            Op::Greater => ">",
+            Op::GreaterEq => ">=",
+            Op::Less => "<",
+            Op::LessEq => "<=",
+            Op::Tilde => "~",
+            Op::Caret => "^",
+            Op::Wildcard => "",
+        })?;
+
+        write!(f, "{}", self.major)?;
+
+        for c in [self.minor, self.patch, self.revision] {
+            match c {
+                Some(c) => write!(f, ".{}", c)?,
+                None => {
+                    if self.op == Op::Wildcard {
+                        f.write_str(".*")?;
+                    }
+
+                    break;
+                }
+            }
+        }
+
+        if let Some(prerelease) = &self.prerelease {
+            write!(f, "-{}", prerelease)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn matches_set(set: &[Comparator], version: &Version) -> bool {
+    if !set.iter().all(|c| c.matches(version)) {
+        return false;
+    }
+
+    // Prerelease versions are only matched if explicitly opted into.
+    version.prerelease.is_none() || set.iter().any(|c| c.allows_prerelease_of(version))
+}
+
+/// Parser for a single comparator set, keeping track of the current position for error reporting.
+struct ReqParser<'a> {
+    input: &'a str,
+    position: usize,
+    end: usize,
+}
+
+impl<'a> ReqParser<'a> {
+    fn new(input: &'a str, start: usize, end: usize) -> Self {
+        Self {
+            input,
+            position: start,
+            end,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..self.end].chars().next()
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.input[self.position..self.end].starts_with(s) {
+            self.position += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_separators(&mut self, allow_comma: bool) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || (allow_comma && c == ',') {
+                self.position += c.len_utf8();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn unexpected(&self) -> VersionReqParseError {
+        match self.peek() {
+            Some(character) => VersionReqParseError::UnexpectedCharacter {
+                position: self.position,
+                character,
+            },
+            None => VersionReqParseError::UnexpectedEnd,
+        }
+    }
+
+    fn parse_set(mut self) -> Result<Vec<Comparator>, VersionReqParseError> {
+        let start = self.position;
+        let mut comparators = Vec::new();
+        let mut any_wildcard = false;
+
+        self.skip_separators(false);
+
+        while self.peek().is_some() {
+            match self.parse_comparator()? {
+                Some(comparator) => comparators.push(comparator),
+                None => any_wildcard = true,
+            }
+
+            self.skip_separators(true);
+        }
+
+        if comparators.is_empty() && !any_wildcard {
+            return Err(VersionReqParseError::EmptySet(start));
+        }
+
+        Ok(comparators)
+    }
+
+    /// Parse a single comparator, returning None if it matches any version.
+    fn parse_comparator(&mut self) -> Result<Option<Comparator>, VersionReqParseError> {
+        let op = if self.eat(">=") {
+            Some(Op::GreaterEq)
+        } else if self.eat("<=") {
+            Some(Op::LessEq)
+        } else if self.eat(">") {
+            Some(Op::Greater)
+        } else if self.eat("<") {
+            Some(Op::Less)
+        } else if self.eat("=") {
+            Some(Op::Exact)
+        } else if self.eat("~") {
+            Some(Op::Tilde)
+        } else if self.eat("^") {
+            Some(Op::Caret)
+        } else {
+            None
+        };
+
+        self.skip_separators(false);
+
+        // Allow a leading 'v', as commonly used in tags.
+        self.eat("v");
+
+        let Some(major) = self.parse_component()? else {
+            return Ok(None);
+        };
+
+        let mut parts: [Option<u32>; 3] = [None; 3];
+        let mut wildcard = false;
+
+        for part in parts.iter_mut() {
+            if !self.eat(".") {
+                break;
+            }
+
+            let start = self.position;
+
+            match self.parse_component()? {
+                Some(n) if !wildcard => *part = Some(n),
+                Some(_) => return Err(self.unexpected_at(start)),
+                None => wildcard = true,
+            }
+        }
+
+        let [minor, patch, revision] = parts;
+
+        let prerelease = if self.peek() == Some('-') {
+            if patch.is_none() {
+                return Err(VersionReqParseError::PartialPrerelease(self.position));
+            }
+
+            self.position += 1;
+
+            Some(self.parse_prerelease()?)
+        } else {
+            None
+        };
+
+        // Build metadata has no effect on matching, so it is skipped.
+        if self.eat("+") {
+            while let Some(c) = self.peek() {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    self.position += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        match self.peek() {
+            None => {}
+            Some(c) if c.is_whitespace() || c == ',' => {}
+            Some(_) => return Err(self.unexpected()),
+        }
+
+        let op = match op {
+            Some(op) => op,
+            None if wildcard => Op::Wildcard,
+            None => Op::Caret,
+        };
+
+        Ok(Some(Comparator {
+            op,
+            major,
+            minor,
+            patch,
+            revision,
+            prerelease,
+        }))
+    }
+
+    fn unexpected_at(&self, position: usize) -> VersionReqParseError {
+        match self.input[position..].chars().next() {
+            Some(character) => VersionReqParseError::UnexpectedCharacter { position, character },
+            None => VersionReqParseError::UnexpectedEnd,
+        }
+    }
+
+    /// Parse a numeric version component, returning None if it is a wildcard.
+    fn parse_component(&mut self) -> Result<Option<u32>, VersionReqParseError> {
+        if self.eat("*") || self.eat("x") || self.eat("X") {
+            return Ok(None);
+        }
+
+        let start = self.position;
+        let digits = self.input[start..self.end]
+            .bytes()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+
+        if digits == 0 {
+            return Err(self.unexpected());
+        }
+
+        let digits = &self.input[start..start + digits];
+
+        if digits.len() > 1 && digits.starts_with('0') {
+            return Err(VersionReqParseError::LeadingZero(start));
+        }
+
+        let n = digits
+            .parse::<u32>()
+            .map_err(|_| VersionReqParseError::NumberTooLarge(start))?;
+
+        self.position += digits.len();
+
+        Ok(Some(n))
+    }
+
+    fn parse_prerelease(&mut self) -> Result<String, VersionReqParseError> {
+        let start = self.position;
+        let mut identifier_start = start;
+
+        loop {
+            match self.peek() {
+                Some(c) if c.is_ascii_alphanumeric() || c == '-' => self.position += 1,
+                Some('.') => {
+                    if self.position == identifier_start {
+                        return Err(VersionReqParseError::EmptyIdentifier(self.position));
+                    }
+
+                    self.position += 1;
+                    identifier_start = self.position;
+                }
+                _ => break,
+            }
+        }
+
+        if self.position == identifier_start {
+            return Err(VersionReqParseError::EmptyIdentifier(self.position));
+        }
+
+        Ok(self.input[start..self.position].to_owned())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::version::ParseMode;
+
+    fn matches(req: &str, version: &str) -> bool {
+        req.parse::<VersionReq>()
+            .unwrap()
+            .matches(&Version::parse(version, ParseMode::STRICT.with_revision(true)).unwrap())
+    }
+
+    #[test]
+    /// Test matching caret, tilde and comparison requirements
+    fn test_matches() {
+        assert!(matches("^1.2", "1.2.0"));
+        assert!(matches("^1.2", "1.9.3"));
+        assert!(!matches("^1.2", "2.0.0"));
+        assert!(!matches("^1.2", "1.1.9"));
+        assert!(matches("^0.2.3", "0.2.9"));
+        assert!(!matches("^0.2.3", "0.3.0"));
+        assert!(matches("^0.0.3", "0.0.3"));
+        assert!(!matches("^0.0.3", "0.0.4"));
+        assert!(matches("1.2.3", "1.4.0"));
+
+        assert!(matches("~1.4.1", "1.4.7"));
+        assert!(!matches("~1.4.1", "1.4.0"));
+        assert!(!matches("~1.4.1", "1.5.0"));
+        assert!(matches("~1", "1.9.0"));
+
+        assert!(matches(">=2.0.0-0, <3", "2.0.0-beta.1"));
+        assert!(matches(">=2.0.0-0, <3", "2.5.0"));
+        assert!(!matches(">=2.0.0-0, <3", "3.0.0"));
+        assert!(!matches(">=2.0.0-0, <3", "3.0.0-alpha.1"));
+        assert!(matches(">= 1.2 < 1.5", "1.4.9"));
+        assert!(!matches(">1.2", "1.2.9"));
+        assert!(matches("<=1.2", "1.2.9"));
+        assert!(matches("=1.2.3", "1.2.3+build.5"));
+        assert!(matches("=1.2.3", "1.2.3.4"));
+        assert!(!matches("=1.2.3.4", "1.2.3.5"));
+
+        assert!(matches("*", "5.0.0"));
+        assert!(matches("1.x", "1.7.0"));
+        assert!(!matches("1.2.*", "1.3.0"));
+        assert!(matches("^1 || ^3", "3.1.0"));
+        assert!(!matches("^1 || ^3", "2.1.0"));
+    }
+
+    #[test]
+    /// Test that prerelease versions only match when opted into for the same version
+    fn test_matches_prerelease() {
+        assert!(!matches("^1.2", "1.3.0-beta.1"));
+        assert!(matches("^1.2.0-beta", "1.2.0-beta.2"));
+        assert!(!matches("^1.2.0-beta", "1.3.0-beta.2"));
+        assert!(!matches(">=1.2.0-beta.3", "1.2.0-beta.2"));
+        assert!(!matches("*", "1.0.0-rc.1"));
+    }
+
+    #[test]
+    /// Test that requirements round-trip when parsed and converted back to a string
+    fn test_display() {
+        let display = |s: &str| s.parse::<VersionReq>().unwrap().to_string();
+
+        assert_eq!(display(">=2.0.0-0, <3"), ">=2.0.0-0, <3");
+        assert_eq!(display("1.2"), "^1.2");
+        assert_eq!(display("1.x"), "1.*");
+        assert_eq!(display("~1 || *"), "~1 || *");
+    }
+
+    #[test]
+    /// Test that invalid requirements are rejected with the correct error
+    fn test_parse_errors() {
+        let error = |s: &str| s.parse::<VersionReq>().unwrap_err();
+
+        assert_eq!(error(""), VersionReqParseError::Empty);
+        assert_eq!(error("^1 ||"), VersionReqParseError::EmptySet(5));
+        assert_eq!(error(">="), VersionReqParseError::UnexpectedEnd);
+        assert_eq!(error("^01.2"), VersionReqParseError::LeadingZero(1));
+        assert_eq!(error("1.2-beta"), VersionReqParseError::PartialPrerelease(3));
+        assert_eq!(error("1.2.3-beta..1"), VersionReqParseError::EmptyIdentifier(11));
+        assert_eq!(
+            error("1.*.3"),
+            VersionReqParseError::UnexpectedCharacter {
+                position: 4,
+                character: '3'
+            }
+        );
+        assert_eq!(
+            error("^1.2; <2"),
+            VersionReqParseError::UnexpectedCharacter {
+                position: 4,
+                character: ';'
+            }
+        );
+    }
+}
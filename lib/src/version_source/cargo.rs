@@ -1,16 +1,20 @@
 use std::borrow::Cow;
-use std::io::{Read, Write};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-const CARGO_FILE_NAME: &str = "Cargo.toml";
-
-use tracing::info;
+use tracing::{debug, info, warn};
 
 use crate::project::VutError;
 use crate::util;
-use crate::version::Version;
+use crate::version::{ParseMode, Version};
 use crate::version_source::VersionSource;
 
+const CARGO_FILE_NAME: &str = "Cargo.toml";
+const CARGO_LOCK_FILE_NAME: &str = "Cargo.lock";
+const PACKAGE_TABLE: &str = "package";
+const WORKSPACE_PACKAGE_TABLE: &str = "workspace.package";
+const DEPENDENCY_TABLES: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
 pub struct CargoSource {
     pub path: PathBuf,
     pub cargo_file_path: PathBuf,
@@ -31,21 +35,17 @@ impl CargoSource {
     }
 
     fn read_cargo_file(&self) -> Result<String, VutError> {
-        let mut file = util::open_file(&self.cargo_file_path).map_err(VutError::VersionFileOpen)?;
-
-        let mut toml_str = String::new();
-
-        file.read_to_string(&mut toml_str).map_err(VutError::VersionFileRead)?;
+        util::read_version_file(&self.cargo_file_path)
+    }
 
-        Ok(toml_str)
+    fn read_cargo_document(&self) -> Result<toml_edit::DocumentMut, VutError> {
+        self.read_cargo_file()?
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|err| VutError::Other(Cow::Owned(err.to_string())))
     }
 
     fn write_cargo_file(&mut self, toml_str: &str) -> Result<(), VutError> {
-        let mut file = util::create_file(&self.cargo_file_path).map_err(VutError::VersionFileOpen)?;
-
-        file.write(toml_str.as_bytes()).map_err(VutError::VersionFileWrite)?;
-
-        Ok(())
+        util::write_version_file(&self.cargo_file_path, toml_str)
     }
 }
 
@@ -58,7 +58,7 @@ impl VersionSource for CargoSource {
         self.cargo_file_path.exists()
     }
 
-    fn get_version(&self) -> Result<Version, VutError> {
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
         let version_str = {
             // Read TOML from cargo file
             let toml_str = self.read_cargo_file()?;
@@ -68,21 +68,42 @@ impl VersionSource for CargoSource {
                 .parse::<toml_edit::DocumentMut>()
                 .map_err(|err| VutError::Other(Cow::Owned(err.to_string())))?;
 
-            // Get version string
-            match doc["package"]["version"].as_str() {
-                Some(version_str) => version_str.to_owned(),
-                _ => {
+            // Get version string, preferring the package's own version over the workspace version
+            let location = match (package_version(&doc), workspace_version(&doc)) {
+                (PackageVersion::Literal(version_str), _) => Some((version_str, PACKAGE_TABLE)),
+                (_, Some(version_str)) => Some((version_str, WORKSPACE_PACKAGE_TABLE)),
+                (PackageVersion::Inherited, None) => {
                     info!(
-                        "No version number found in '{}'. This Cargo.toml may be a workspace, and cannot be used as a version source.",
+                        "The package in '{}' inherits its version from the workspace, and cannot be used as a version source.",
                         self.cargo_file_path.display()
                     );
-                    return Err(VutError::VersionNotFound);
+                    None
                 }
+                (PackageVersion::None, None) => {
+                    info!(
+                        "No version number found in '{}'. This Cargo.toml may be a workspace without a [{}] version, and cannot be used as a version source.",
+                        self.cargo_file_path.display(),
+                        WORKSPACE_PACKAGE_TABLE
+                    );
+                    None
+                }
+            };
+
+            match location {
+                Some((version_str, table)) => {
+                    info!(
+                        "Using version from [{}] in '{}'.",
+                        table,
+                        self.cargo_file_path.display()
+                    );
+                    version_str.to_owned()
+                }
+                None => return Err(VutError::VersionNotFound),
             }
         };
 
         // Parse version string
-        let version = version_str.parse().map_err(|err| VutError::Other(Cow::Owned(err)))?;
+        let version = Version::parse(&version_str, parse_mode).map_err(VutError::VersionParse)?;
 
         Ok(version)
     }
@@ -96,14 +117,58 @@ impl VersionSource for CargoSource {
             .parse::<toml_edit::DocumentMut>()
             .map_err(|err| VutError::Other(Cow::Owned(err.to_string())))?;
 
-        // If the file does not contain a [package] section, don't try to update version.
-        // This will typically be the case if the Cargo.toml is a workspace.
-        if !doc.as_table().contains_key("package") {
+        let update_package = match package_version(&doc) {
+            PackageVersion::Literal(_) => true,
+            PackageVersion::Inherited => {
+                // Don't replace the inherited version with a literal.
+                // It will be updated through the workspace.
+                debug!(
+                    "Skipping inherited package version in '{}'.",
+                    self.cargo_file_path.display()
+                );
+                false
+            }
+            // If the file does not contain a [package] version, don't try to add one.
+            // This will typically be the case if the Cargo.toml is a workspace.
+            PackageVersion::None => {
+                debug!(
+                    "No [{}] version found in '{}'.",
+                    PACKAGE_TABLE,
+                    self.cargo_file_path.display()
+                );
+                false
+            }
+        };
+        let update_workspace = workspace_version(&doc).is_some();
+
+        if !update_package && !update_workspace {
+            info!(
+                "Skipping '{}', as it has no [{}] or [{}] version to update.",
+                self.cargo_file_path.display(),
+                PACKAGE_TABLE,
+                WORKSPACE_PACKAGE_TABLE
+            );
             return Ok(());
         }
 
-        // Replace version number
-        doc["package"]["version"] = toml_edit::value(version.to_string());
+        // Replace version numbers
+        if update_package {
+            info!(
+                "Updating version in [{}] in '{}'.",
+                PACKAGE_TABLE,
+                self.cargo_file_path.display()
+            );
+            util::set_toml_string(&mut doc["package"]["version"], &version.to_string());
+        }
+
+        if update_workspace {
+            info!(
+                "Updating version in [{}] in '{}'.",
+                WORKSPACE_PACKAGE_TABLE,
+                self.cargo_file_path.display()
+            );
+            util::set_toml_string(&mut doc["workspace"]["package"]["version"], &version.to_string());
+        }
 
         // Serialize updated document to string
         let toml_str = doc.to_string();
@@ -113,4 +178,534 @@ impl VersionSource for CargoSource {
 
         Ok(())
     }
+
+    fn supports_revision(&self) -> bool {
+        // Cargo requires SemVer versions
+        false
+    }
+
+    fn update_dependencies(&mut self, updated_paths: &[PathBuf], version: &Version) -> Result<(), VutError> {
+        // Read TOML from cargo file
+        let toml_str = self.read_cargo_file()?;
+
+        // Parse as document
+        let mut doc = toml_str
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|err| VutError::Other(Cow::Owned(err.to_string())))?;
+
+        // Collect all dependency tables, including target-specific and workspace dependencies
+        let mut dependency_tables: Vec<&mut dyn toml_edit::TableLike> = Vec::new();
+        let root = doc.as_table_mut();
+
+        for (key, item) in root.iter_mut() {
+            match key.get() {
+                "target" => {
+                    if let Some(targets) = item.as_table_like_mut() {
+                        for (_, target) in targets.iter_mut() {
+                            if let Some(target) = target.as_table_like_mut() {
+                                for (key, item) in target.iter_mut() {
+                                    if DEPENDENCY_TABLES.contains(&key.get())
+                                        && let Some(table) = item.as_table_like_mut()
+                                    {
+                                        dependency_tables.push(table);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                "workspace" => {
+                    if let Some(table) = item.get_mut("dependencies").and_then(|d| d.as_table_like_mut()) {
+                        dependency_tables.push(table);
+                    }
+                }
+                key if DEPENDENCY_TABLES.contains(&key) => {
+                    if let Some(table) = item.as_table_like_mut() {
+                        dependency_tables.push(table);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut changed = false;
+
+        for table in dependency_tables {
+            for (name, dependency) in table.iter_mut() {
+                if update_path_dependency(&self.path, dependency, updated_paths, version) {
+                    debug!(
+                        "Updated version requirement of dependency '{}' in '{}'.",
+                        name.get(),
+                        self.cargo_file_path.display()
+                    );
+                    changed = true;
+                }
+            }
+        }
+
+        if changed {
+            // Overwrite cargo file
+            self.write_cargo_file(&doc.to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Update the version requirement of a path dependency, if it points to one of the updated paths.
+/// Returns true if the requirement was changed.
+fn update_path_dependency(
+    manifest_path: &Path,
+    dependency: &mut toml_edit::Item,
+    updated_paths: &[PathBuf],
+    version: &Version,
+) -> bool {
+    let Some(dependency) = dependency.as_table_like_mut() else {
+        return false;
+    };
+
+    let Some(path) = dependency.get("path").and_then(|p| p.as_str()) else {
+        return false;
+    };
+
+    let path = util::normalize_path(manifest_path.join(path));
+    if !updated_paths.contains(&path) {
+        return false;
+    }
+
+    let Some(version_item) = dependency.get_mut("version") else {
+        return false;
+    };
+
+    let Some(requirement) = version_item.as_str() else {
+        return false;
+    };
+
+    match update_requirement(requirement, version) {
+        Some(new_requirement) if new_requirement != requirement => {
+            util::set_toml_string(version_item, &new_requirement);
+            true
+        }
+        Some(_) => false,
+        None => {
+            warn!(
+                "Cannot update version requirement '{}' for path dependency '{}', as it is not a single complete version with an =, ^ or ~ operator.",
+                requirement,
+                path.display()
+            );
+            false
+        }
+    }
+}
+
+/// Replace the version in a simple version requirement (e.g. "0.2.0", "=0.2.0", "^0.2.0" or "~0.2.0"),
+/// keeping its operator.
+/// Returns None if the requirement is too complex to update, uses a comparison operator,
+/// or does not contain a complete version.
+fn update_requirement(requirement: &str, version: &Version) -> Option<String> {
+    let requirement = requirement.trim();
+    let version_start = requirement
+        .find(|c: char| !matches!(c, '=' | '^' | '~' | '>' | '<') && !c.is_whitespace())
+        .unwrap_or(requirement.len());
+    let (operator, old_version) = requirement.split_at(version_start);
+    let operator = operator.trim();
+
+    // Comparison operators would exclude the new version (e.g. "<0.3.0") or keep allowing older ones (e.g. ">0.2.0")
+    if !matches!(operator, "" | "=" | "^" | "~") {
+        return None;
+    }
+
+    // Only update requirements that contain a single complete version
+    old_version.parse::<Version>().ok()?;
+
+    // Build metadata is ignored by Cargo in version requirements, so it is left out.
+    let version = Version {
+        build: None,
+        ..version.clone()
+    };
+
+    Some(format!("{}{}", operator, version))
+}
+
+/// Update the versions of crates at the specified paths in their Cargo.lock files, without invoking Cargo.
+/// Only crates that currently have the specified version are updated.
+pub fn sync_cargo_lockfiles(root_path: &Path, updated_paths: &[PathBuf], version: &Version) -> Result<(), VutError> {
+    let version_str = version.to_string();
+
+    // Members that inherit an updated workspace version are updated along with it,
+    // even if they are not version sources themselves, as in a virtual workspace.
+    let mut paths = updated_paths.to_vec();
+
+    for path in updated_paths {
+        let Some(source) = CargoSource::from_path(path) else {
+            continue;
+        };
+
+        let doc = source.read_cargo_document()?;
+
+        if workspace_version(&doc) == Some(version_str.as_str()) {
+            for member_path in find_inheriting_members(path, &doc)? {
+                if !paths.contains(&member_path) {
+                    paths.push(member_path);
+                }
+            }
+        }
+    }
+
+    // Crate names to update, grouped by the Cargo.lock they are in
+    let mut lockfiles: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+
+    for path in &paths {
+        let Some(source) = CargoSource::from_path(path) else {
+            continue;
+        };
+
+        let doc = source.read_cargo_document()?;
+
+        let Some(name) = doc.get("package").and_then(|p| p.get("name")).and_then(|n| n.as_str()) else {
+            continue;
+        };
+
+        let current_version = match package_version(&doc) {
+            PackageVersion::Literal(version_str) => Some(version_str.to_owned()),
+            PackageVersion::Inherited => find_workspace_version(root_path, path)?,
+            PackageVersion::None => None,
+        };
+
+        if current_version.as_deref() != Some(version_str.as_str()) {
+            continue;
+        }
+
+        let Some(lockfile_path) = find_outwards_within(root_path, path, CARGO_LOCK_FILE_NAME) else {
+            continue;
+        };
+
+        lockfiles.entry(lockfile_path).or_default().insert(name.to_owned());
+    }
+
+    for (lockfile_path, names) in lockfiles {
+        update_lockfile(&lockfile_path, &names, &version_str)?;
+    }
+
+    Ok(())
+}
+
+/// Find the members of a workspace whose packages inherit the workspace version.
+fn find_inheriting_members(workspace_path: &Path, doc: &toml_edit::DocumentMut) -> Result<Vec<PathBuf>, VutError> {
+    let members = build_member_globset(doc, "members")?;
+    let exclude = build_member_globset(doc, "exclude")?;
+
+    let mut member_paths = Vec::new();
+
+    let entries = walkdir::WalkDir::new(workspace_path)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+
+            entry.file_type().is_dir() && name != "target" && !name.starts_with('.')
+        })
+        .filter_map(|entry| entry.ok());
+
+    for entry in entries {
+        let Ok(relative_path) = entry.path().strip_prefix(workspace_path) else {
+            continue;
+        };
+
+        if !members.is_match(relative_path) || exclude.is_match(relative_path) {
+            continue;
+        }
+
+        let Some(source) = CargoSource::from_path(entry.path()) else {
+            continue;
+        };
+
+        if matches!(
+            package_version(&source.read_cargo_document()?),
+            PackageVersion::Inherited
+        ) {
+            member_paths.push(util::normalize_path(entry.path()));
+        }
+    }
+
+    Ok(member_paths)
+}
+
+/// Build a glob set from a list of member paths in the [workspace] table, such as `members` or `exclude`.
+fn build_member_globset(doc: &toml_edit::DocumentMut, key: &str) -> Result<globset::GlobSet, VutError> {
+    let mut builder = globset::GlobSetBuilder::new();
+
+    let patterns = doc
+        .get("workspace")
+        .and_then(|w| w.get(key))
+        .and_then(|m| m.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|p| p.as_str());
+
+    for pattern in patterns {
+        let glob = globset::GlobBuilder::new(pattern.trim_end_matches('/'))
+            .literal_separator(true)
+            .build()
+            .map_err(|err| VutError::Other(Cow::Owned(err.to_string())))?;
+
+        builder.add(glob);
+    }
+
+    builder
+        .build()
+        .map_err(|err| VutError::Other(Cow::Owned(err.to_string())))
+}
+
+/// Find the nearest file with the specified name in the specified path or its parents,
+/// without leaving the root path.
+fn find_outwards_within(root_path: &Path, path: &Path, file_name: &str) -> Option<PathBuf> {
+    path.ancestors()
+        .take_while(|p| p.starts_with(root_path))
+        .map(|p| p.join(file_name))
+        .find(|p| p.is_file())
+}
+
+/// Find the workspace version that a package at the specified path inherits.
+fn find_workspace_version(root_path: &Path, path: &Path) -> Result<Option<String>, VutError> {
+    for ancestor in path.ancestors().skip(1).take_while(|p| p.starts_with(root_path)) {
+        if let Some(source) = CargoSource::from_path(ancestor) {
+            let doc = source.read_cargo_document()?;
+
+            if let Some(version_str) = workspace_version(&doc) {
+                return Ok(Some(version_str.to_owned()));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Update the version of the specified local crates in a Cargo.lock file.
+fn update_lockfile(lockfile_path: &Path, names: &HashSet<String>, version_str: &str) -> Result<(), VutError> {
+    let toml_str = util::read_version_file(lockfile_path)?;
+
+    let mut doc = toml_str
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|err| VutError::Other(Cow::Owned(err.to_string())))?;
+
+    if update_lockfile_document(&mut doc, names, version_str) {
+        info!("Updating '{}'.", lockfile_path.display());
+
+        util::write_version_file(lockfile_path, &doc.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Update the version of the specified local crates in a parsed Cargo.lock document.
+/// Returns true if anything was changed.
+fn update_lockfile_document(doc: &mut toml_edit::DocumentMut, names: &HashSet<String>, version_str: &str) -> bool {
+    let Some(packages) = doc.get_mut("package").and_then(|p| p.as_array_of_tables_mut()) else {
+        return false;
+    };
+
+    // Old versions of the updated local crates
+    let mut old_versions: HashMap<String, String> = HashMap::new();
+
+    for package in packages.iter_mut() {
+        // Local crates have no source
+        if package.contains_key("source") {
+            continue;
+        }
+
+        if let Some(name) = package.get("name").and_then(|n| n.as_str())
+            && names.contains(name)
+            && let Some(old_version) = package.get("version").and_then(|v| v.as_str())
+            && old_version != version_str
+        {
+            debug!("Updating locked version of '{}'.", name);
+            old_versions.insert(name.to_owned(), old_version.to_owned());
+            util::set_toml_string(&mut package["version"], version_str);
+        }
+    }
+
+    // Dependencies only include the version if there are multiple versions of a crate, e.g. "name 1.0.0".
+    for package in packages.iter_mut() {
+        let Some(dependencies) = package.get_mut("dependencies").and_then(|d| d.as_array_mut()) else {
+            continue;
+        };
+
+        for dependency in dependencies.iter_mut() {
+            let new_dependency = match dependency.as_str().and_then(|d| d.split_once(' ')) {
+                Some((name, old_version)) if old_versions.get(name).is_some_and(|v| v == old_version) => {
+                    format!("{} {}", name, version_str)
+                }
+                _ => continue,
+            };
+
+            let decor = dependency.decor().clone();
+            *dependency = new_dependency.into();
+            *dependency.decor_mut() = decor;
+        }
+    }
+
+    !old_versions.is_empty()
+}
+
+/// Version of the [package] table in a Cargo.toml.
+enum PackageVersion<'a> {
+    /// No package or package version is present.
+    None,
+    /// The version is specified directly.
+    Literal(&'a str),
+    /// The version is inherited from the workspace, using `version.workspace = true`.
+    Inherited,
+}
+
+fn package_version(doc: &toml_edit::DocumentMut) -> PackageVersion<'_> {
+    let Some(version) = doc.get("package").and_then(|p| p.get("version")) else {
+        return PackageVersion::None;
+    };
+
+    if let Some(version_str) = version.as_str() {
+        PackageVersion::Literal(version_str)
+    } else if version.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+        PackageVersion::Inherited
+    } else {
+        PackageVersion::None
+    }
+}
+
+fn workspace_version(doc: &toml_edit::DocumentMut) -> Option<&str> {
+    doc.get("workspace")
+        .and_then(|w| w.get("package"))
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(toml_str: &str) -> toml_edit::DocumentMut {
+        toml_str.parse().unwrap()
+    }
+
+    #[test]
+    /// Test detecting literal, inherited and missing package versions
+    fn test_package_version() {
+        let doc = parse("[package]\nname = \"a\"\nversion = \"1.2.3\"\n");
+        assert!(matches!(package_version(&doc), PackageVersion::Literal("1.2.3")));
+
+        let doc = parse("[package]\nname = \"a\"\nversion.workspace = true\n");
+        assert!(matches!(package_version(&doc), PackageVersion::Inherited));
+
+        let doc = parse("[package]\nname = \"a\"\nversion = { workspace = true }\n");
+        assert!(matches!(package_version(&doc), PackageVersion::Inherited));
+
+        let doc = parse("[workspace]\nmembers = [\"a\"]\n");
+        assert!(matches!(package_version(&doc), PackageVersion::None));
+        assert_eq!(workspace_version(&doc), None);
+    }
+
+    #[test]
+    /// Test reading the workspace package version
+    fn test_workspace_version() {
+        let doc = parse("[workspace]\nmembers = [\"a\"]\n\n[workspace.package]\nversion = \"0.4.0\"\n");
+
+        assert_eq!(workspace_version(&doc), Some("0.4.0"));
+    }
+
+    #[test]
+    /// Test that only local crates are updated in a Cargo.lock
+    fn test_update_lockfile_document() {
+        let mut doc = parse(
+            r#"version = 4
+
+[[package]]
+name = "a"
+version = "0.2.0"
+
+[[package]]
+name = "a"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "b"
+version = "0.2.0"
+dependencies = [
+ "a 0.2.0",
+ "a 1.0.0",
+]
+"#,
+        );
+
+        let names: HashSet<String> = ["a".to_owned(), "b".to_owned()].into_iter().collect();
+
+        assert!(update_lockfile_document(&mut doc, &names, "0.3.0"));
+        assert_eq!(
+            doc.to_string(),
+            r#"version = 4
+
+[[package]]
+name = "a"
+version = "0.3.0"
+
+[[package]]
+name = "a"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "b"
+version = "0.3.0"
+dependencies = [
+ "a 0.3.0",
+ "a 1.0.0",
+]
+"#
+        );
+
+        assert!(!update_lockfile_document(&mut doc, &names, "0.3.0"));
+    }
+
+    #[test]
+    /// Test that simple version requirements are updated while keeping their operator
+    fn test_update_requirement() {
+        let version: Version = "0.3.0+build.1".parse().unwrap();
+
+        assert_eq!(update_requirement("0.2.0", &version), Some("0.3.0".to_owned()));
+        assert_eq!(update_requirement("=0.2.0", &version), Some("=0.3.0".to_owned()));
+        assert_eq!(update_requirement("^ 0.2.0", &version), Some("^0.3.0".to_owned()));
+        assert_eq!(update_requirement("~0.2.1", &version), Some("~0.3.0".to_owned()));
+        assert_eq!(update_requirement("0.2", &version), None);
+        assert_eq!(update_requirement(">0.2.0", &version), None);
+        assert_eq!(update_requirement("<=0.2.0", &version), None);
+        assert_eq!(update_requirement(">=0.2, <0.3", &version), None);
+        assert_eq!(update_requirement("*", &version), None);
+    }
+
+    #[test]
+    /// Test that members inheriting the version of a virtual workspace are updated in Cargo.lock
+    fn test_sync_virtual_workspace_lockfile() {
+        let dir = util::TestDir::new("cargo-virtual-workspace");
+        dir.write(
+            CARGO_FILE_NAME,
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/c\"]\n\n[workspace.package]\nversion = \"0.2.0\"\n",
+        );
+        dir.write(
+            "crates/a/Cargo.toml",
+            "[package]\nname = \"a\"\nversion.workspace = true\n",
+        );
+        dir.write("crates/b/Cargo.toml", "[package]\nname = \"b\"\nversion = \"0.1.0\"\n");
+        dir.write(
+            "crates/c/Cargo.toml",
+            "[package]\nname = \"c\"\nversion.workspace = true\n",
+        );
+
+        let lockfile = "version = 4\n\n[[package]]\nname = \"a\"\nversion = \"0.1.0\"\n\n[[package]]\nname = \"b\"\nversion = \"0.1.0\"\n\n[[package]]\nname = \"c\"\nversion = \"0.1.0\"\n";
+        dir.write(CARGO_LOCK_FILE_NAME, lockfile);
+
+        let root_path = util::normalize_path(dir.path());
+        sync_cargo_lockfiles(&root_path, std::slice::from_ref(&root_path), &"0.2.0".parse().unwrap()).unwrap();
+
+        assert_eq!(dir.read(CARGO_LOCK_FILE_NAME), lockfile.replacen("0.1.0", "0.2.0", 1));
+    }
 }
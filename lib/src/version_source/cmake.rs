@@ -0,0 +1,290 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+const CMAKELISTS_FILE_NAME: &str = "CMakeLists.txt";
+const PROJECT_COMMAND: &str = "project";
+const VERSION_KEYWORD: &str = "VERSION";
+
+use tracing::{info, warn};
+
+use crate::project::VutError;
+use crate::scheme::ecosystem;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Version source for CMake projects.
+pub struct CMakeSource {
+    pub path: PathBuf,
+    pub cmakelists_file_path: PathBuf,
+}
+
+/// An argument of a CMake command.
+#[derive(Debug, Eq, PartialEq)]
+struct Argument {
+    /// Range of the argument, excluding any quotes or brackets.
+    range: Range<usize>,
+    /// Whether the argument is unquoted, which is required for keywords such as VERSION.
+    unquoted: bool,
+}
+
+impl CMakeSource {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let cmakelists_file_path = path.join(CMAKELISTS_FILE_NAME);
+
+        // Only consider projects that declare a version, as most CMakeLists.txt files
+        // in a source tree belong to subdirectories of a project.
+        let text = util::read_version_file(&cmakelists_file_path).ok()?;
+        find_project_version(&text)?;
+
+        Some(Self {
+            path: path.to_path_buf(),
+            cmakelists_file_path,
+        })
+    }
+}
+
+impl VersionSource for CMakeSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.cmakelists_file_path.exists()
+    }
+
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        let text = util::read_version_file(&self.cmakelists_file_path)?;
+
+        let Some(range) = find_project_version(&text) else {
+            info!("No project version found in '{}'.", self.cmakelists_file_path.display());
+            return Err(VutError::VersionNotFound);
+        };
+
+        // CMake allows versions with fewer than three components, such as 1.2
+        let version_str = &text[range];
+        let version_str = match version_str.split('.').count() {
+            1 => Cow::Owned(format!("{}.0.0", version_str)),
+            2 => Cow::Owned(format!("{}.0", version_str)),
+            _ => Cow::Borrowed(version_str),
+        };
+
+        let version = Version::parse(&version_str, parse_mode).map_err(VutError::VersionParse)?;
+
+        Ok(version)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let text = util::read_version_file(&self.cmakelists_file_path)?;
+
+        let range = find_project_version(&text).ok_or_else(|| {
+            VutError::Other(Cow::Owned(format!(
+                "No project version found in '{}'.",
+                self.cmakelists_file_path.display()
+            )))
+        })?;
+
+        // CMake versions can only contain up to four numeric components
+        let version_str = ecosystem::numeric_version(version);
+
+        if version.prerelease.is_some() || version.build.is_some() {
+            warn!(
+                "CMake versions cannot contain a prerelease or build metadata, so '{}' is written as '{}' in '{}'.",
+                version,
+                version_str,
+                self.cmakelists_file_path.display()
+            );
+        }
+
+        let text = util::replace_range(&text, range, &version_str);
+
+        util::write_version_file(&self.cmakelists_file_path, &text)?;
+
+        Ok(())
+    }
+}
+
+/// Find the range of the literal VERSION argument of the first `project()` command that has one.
+/// Versions that refer to variables, such as `${MY_VERSION}`, are ignored.
+fn find_project_version(text: &str) -> Option<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let c = bytes[pos];
+
+        if c == b'#' {
+            pos = skip_comment(bytes, pos);
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            let start = pos;
+            while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
+                pos += 1;
+            }
+
+            let command = &text[start..pos];
+
+            while pos < bytes.len() && matches!(bytes[pos], b' ' | b'\t') {
+                pos += 1;
+            }
+
+            if bytes.get(pos) != Some(&b'(') {
+                continue;
+            }
+
+            let (arguments, end) = parse_arguments(bytes, pos + 1)?;
+            pos = end;
+
+            if !command.eq_ignore_ascii_case(PROJECT_COMMAND) {
+                continue;
+            }
+
+            let version = arguments
+                .iter()
+                .position(|arg| arg.unquoted && &text[arg.range.clone()] == VERSION_KEYWORD)
+                .and_then(|i| arguments.get(i + 1))
+                .map(|arg| arg.range.clone())
+                .filter(|range| !range.is_empty() && !text[range.clone()].contains('$'));
+
+            if version.is_some() {
+                return version;
+            }
+        } else {
+            pos += 1;
+        }
+    }
+
+    None
+}
+
+/// Parse the arguments of a command, starting after the opening parenthesis.
+/// Returns the arguments and the position after the closing parenthesis.
+fn parse_arguments(bytes: &[u8], mut pos: usize) -> Option<(Vec<Argument>, usize)> {
+    let mut arguments = Vec::new();
+    let mut depth = 0;
+
+    loop {
+        match *bytes.get(pos)? {
+            c if c.is_ascii_whitespace() => pos += 1,
+            b'#' => pos = skip_comment(bytes, pos),
+            b'(' => {
+                depth += 1;
+                pos += 1;
+            }
+            b')' => {
+                pos += 1;
+
+                if depth == 0 {
+                    return Some((arguments, pos));
+                }
+
+                depth -= 1;
+            }
+            b'"' => {
+                let start = pos + 1;
+                pos = start;
+
+                loop {
+                    match *bytes.get(pos)? {
+                        b'\\' => pos += 2,
+                        b'"' => break,
+                        _ => pos += 1,
+                    }
+                }
+
+                arguments.push(Argument {
+                    range: start..pos,
+                    unquoted: false,
+                });
+                pos += 1;
+            }
+            b'[' if bracket_open(bytes, pos).is_some() => {
+                let (level, len) = bracket_open(bytes, pos)?;
+                let start = pos + len;
+                let end = find_bracket_close(bytes, start, level)?;
+
+                arguments.push(Argument {
+                    range: start..end,
+                    unquoted: false,
+                });
+                pos = end + level + 2;
+            }
+            _ => {
+                let start = pos;
+
+                while let Some(&c) = bytes.get(pos) {
+                    match c {
+                        b'\\' => pos += 2,
+                        b'(' | b')' | b'#' | b'"' => break,
+                        c if c.is_ascii_whitespace() => break,
+                        _ => pos += 1,
+                    }
+                }
+
+                let pos_end = pos.min(bytes.len());
+                arguments.push(Argument {
+                    range: start..pos_end,
+                    unquoted: true,
+                });
+                pos = pos_end;
+            }
+        }
+    }
+}
+
+/// Skip a line comment or a bracket comment such as `#[[ ... ]]`, starting at the `#`.
+fn skip_comment(bytes: &[u8], pos: usize) -> usize {
+    if let Some((level, len)) = bracket_open(bytes, pos + 1) {
+        if let Some(end) = find_bracket_close(bytes, pos + 1 + len, level) {
+            return end + level + 2;
+        }
+
+        return bytes.len();
+    }
+
+    bytes[pos..]
+        .iter()
+        .position(|&c| c == b'\n')
+        .map_or(bytes.len(), |i| pos + i + 1)
+}
+
+/// Get the level (number of `=`) and length of an opening bracket such as `[==[` at the specified position.
+fn bracket_open(bytes: &[u8], pos: usize) -> Option<(usize, usize)> {
+    if bytes.get(pos) != Some(&b'[') {
+        return None;
+    }
+
+    let level = bytes[pos + 1..].iter().take_while(|&&c| c == b'=').count();
+
+    (bytes.get(pos + 1 + level) == Some(&b'[')).then_some((level, level + 2))
+}
+
+/// Find the position of the closing bracket of the specified level.
+fn find_bracket_close(bytes: &[u8], start: usize, level: usize) -> Option<usize> {
+    let close = format!("]{}]", "=".repeat(level));
+
+    bytes[start..]
+        .windows(close.len())
+        .position(|w| w == close.as_bytes())
+        .map(|i| start + i)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CMAKELISTS: &str = "cmake_minimum_required(VERSION 3.20)\r\n#[[ project(Old VERSION 0.1.0) ]]\r\nset(DESCRIPTION \"project(Fake VERSION 0.2.0)\")\r\n\r\nproject(\r\n  MyLib # the library\r\n  VERSION\r\n    \"1.2.3\"\r\n  LANGUAGES CXX\r\n)\r\n";
+
+    #[test]
+    /// Test finding the project version, ignoring comments, strings and other commands
+    fn test_find_project_version() {
+        let range = find_project_version(CMAKELISTS).unwrap();
+        assert_eq!(&CMAKELISTS[range], "1.2.3");
+
+        let text = "PROJECT(MyLib LANGUAGES C VERSION 1.2.3.4)";
+        assert_eq!(&text[find_project_version(text).unwrap()], "1.2.3.4");
+
+        assert_eq!(find_project_version("project(MyLib VERSION ${MYLIB_VERSION})"), None);
+        assert_eq!(find_project_version("project(MyLib LANGUAGES CXX)"), None);
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+
+const PACKAGE_FILE_NAME: &str = "composer.json";
+
+use crate::project::VutError;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Version source for PHP Composer packages.
+pub struct ComposerSource {
+    pub path: PathBuf,
+    pub package_file_path: PathBuf,
+}
+
+impl ComposerSource {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let package_file_path = path.join(PACKAGE_FILE_NAME);
+
+        // The version is optional in composer.json, and usually determined from version control,
+        // so only consider packages that actually specify one.
+        let json_str = util::read_version_file(&package_file_path).ok()?;
+        util::parse_json(&json_str).ok()?["version"].as_str()?;
+
+        Some(Self {
+            path: path.to_path_buf(),
+            package_file_path,
+        })
+    }
+
+    fn read_package_file(&self) -> Result<String, VutError> {
+        util::read_version_file(&self.package_file_path)
+    }
+
+    fn write_package_file(&mut self, json_str: &str) -> Result<(), VutError> {
+        util::write_version_file(&self.package_file_path, json_str)
+    }
+}
+
+impl VersionSource for ComposerSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.package_file_path.exists()
+    }
+
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        let version_str = {
+            let package = util::parse_json(&self.read_package_file()?)?;
+
+            // Get version string
+            let version_str = package["version"]
+                .as_str()
+                .ok_or_else(|| VutError::Other("No version property found!".into()))?;
+
+            version_str.to_owned()
+        };
+
+        // Parse version string
+        let version = Version::parse(&version_str, parse_mode).map_err(VutError::VersionParse)?;
+
+        Ok(version)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let json_str = self.read_package_file()?;
+
+        // Make sure the file is valid JSON before editing it
+        util::parse_json(&json_str)?;
+
+        // Replace version number in place, preserving the formatting of the rest of the file
+        let json_str = util::set_json_string(&json_str, &["version"], &version.to_string())
+            .ok_or_else(|| VutError::Other("No version property found!".into()))?;
+
+        self.write_package_file(&json_str)?;
+
+        Ok(())
+    }
+
+    fn supports_revision(&self) -> bool {
+        // Composer normalizes four-part versions, but Packagist and other tools expect SemVer
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Test reading and updating the version of a package, and ignoring packages without one
+    fn test_get_set_version() {
+        let dir = util::TestDir::new("composer");
+
+        let package_json = "{\r\n    \"name\": \"vendor/package\",\r\n    \"version\": \"1.2.3\",\r\n    \"require\": {\r\n        \"php\": \">=8.1\"\r\n    }\r\n}\r\n";
+        dir.write(Path::new("package").join(PACKAGE_FILE_NAME), package_json);
+        dir.write(
+            Path::new("versionless").join(PACKAGE_FILE_NAME),
+            "{\n    \"name\": \"vendor/versionless\"\n}\n",
+        );
+
+        assert!(ComposerSource::from_path(&dir.path().join("versionless")).is_none());
+
+        let mut source = ComposerSource::from_path(&dir.path().join("package")).unwrap();
+        assert_eq!(source.get_version(ParseMode::STRICT).unwrap(), "1.2.3".parse().unwrap());
+
+        source.set_version(&"1.3.0-beta.1".parse().unwrap()).unwrap();
+        assert_eq!(
+            dir.read(Path::new("package").join(PACKAGE_FILE_NAME)),
+            package_json.replace("1.2.3", "1.3.0-beta.1")
+        );
+    }
+}
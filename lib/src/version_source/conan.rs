@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+const CONANFILE_FILE_NAME: &str = "conanfile.py";
+const CONANDATA_FILE_NAME: &str = "conandata.yml";
+const VERSION_KEY: &str = "version";
+
+use regex::Regex;
+use tracing::info;
+
+use crate::project::VutError;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Matches the definition of a recipe class, e.g. `class MyLibConan(ConanFile):`.
+static REGEX_CONANFILE_CLASS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^class[ \t]+\w+[ \t]*\([^)]*\bConanFile\b[^)]*\)[ \t]*:"#).unwrap());
+
+/// Matches a literal version attribute with its indentation removed, e.g. `version = "1.2.3"`.
+static REGEX_CONANFILE_VERSION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^version[ \t]*=[ \t]*(?:"(?P<double>[^"\\\r\n]*)"|'(?P<single>[^'\\\r\n]*)')"#).unwrap()
+});
+
+/// Where the version of a recipe is specified.
+enum VersionLocation {
+    /// A literal version attribute in conanfile.py.
+    ConanFile,
+    /// A top-level version in conandata.yml, typically read by `set_version()`.
+    ConanData(PathBuf),
+}
+
+/// Version source for Conan recipes.
+pub struct ConanSource {
+    pub path: PathBuf,
+    pub conanfile_file_path: PathBuf,
+    version_location: VersionLocation,
+}
+
+impl ConanSource {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let conanfile_file_path = path.join(CONANFILE_FILE_NAME);
+        let conanfile = util::read_version_file(&conanfile_file_path).ok()?;
+
+        let version_location = if find_conanfile_version(&conanfile).is_some() {
+            VersionLocation::ConanFile
+        } else {
+            // Recipes without a literal version may read it from conandata.yml instead
+            let conandata_file_path = path.join(CONANDATA_FILE_NAME);
+            let conandata = util::read_version_file(&conandata_file_path).ok()?;
+            util::get_yaml_string(&conandata, VERSION_KEY)?;
+
+            VersionLocation::ConanData(conandata_file_path)
+        };
+
+        Some(Self {
+            path: path.to_path_buf(),
+            conanfile_file_path,
+            version_location,
+        })
+    }
+
+    /// Get the path of the file containing the version.
+    fn version_file_path(&self) -> &Path {
+        match &self.version_location {
+            VersionLocation::ConanFile => &self.conanfile_file_path,
+            VersionLocation::ConanData(path) => path,
+        }
+    }
+}
+
+impl VersionSource for ConanSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.version_file_path().exists()
+    }
+
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        let text = util::read_version_file(self.version_file_path())?;
+
+        let version_str = match self.version_location {
+            VersionLocation::ConanFile => find_conanfile_version(&text).map(|range| text[range].to_owned()),
+            VersionLocation::ConanData(_) => util::get_yaml_string(&text, VERSION_KEY),
+        };
+
+        let Some(version_str) = version_str else {
+            info!("No version number found in '{}'.", self.version_file_path().display());
+            return Err(VutError::VersionNotFound);
+        };
+
+        let version = Version::parse(&version_str, parse_mode).map_err(VutError::VersionParse)?;
+
+        Ok(version)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let text = util::read_version_file(self.version_file_path())?;
+
+        let text = match self.version_location {
+            VersionLocation::ConanFile => {
+                let range = find_conanfile_version(&text).ok_or_else(|| {
+                    VutError::Other(Cow::Owned(format!(
+                        "No version number found in '{}'.",
+                        self.conanfile_file_path.display()
+                    )))
+                })?;
+
+                util::replace_range(&text, range, &version.to_string())
+            }
+            VersionLocation::ConanData(_) => util::set_yaml_string(&text, VERSION_KEY, &version.to_string()),
+        };
+
+        util::write_version_file(self.version_file_path(), &text)?;
+
+        Ok(())
+    }
+}
+
+/// Find the range of the literal version attribute in a conanfile.py, excluding the quotes.
+/// Only class-level attributes of the ConanFile subclass are considered, so that assignments
+/// in methods, such as `self.version = ...` in `set_version()`, or in other classes are ignored.
+fn find_conanfile_version(text: &str) -> Option<Range<usize>> {
+    let mut offset = 0;
+    let mut in_class = false;
+    // Indentation of the class body, determined by its first statement
+    let mut body_indent: Option<&str> = None;
+
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let statement = line.trim_start_matches([' ', '\t']);
+        let indent = &line[..line.len() - statement.len()];
+
+        // Blank lines and comments don't affect which block a line belongs to
+        if statement.trim().is_empty() || statement.starts_with('#') {
+            continue;
+        }
+
+        if indent.is_empty() {
+            in_class = REGEX_CONANFILE_CLASS.is_match(statement);
+            body_indent = None;
+            continue;
+        }
+
+        if !in_class {
+            continue;
+        }
+
+        if *body_indent.get_or_insert(indent) != indent {
+            continue;
+        }
+
+        if let Some(caps) = REGEX_CONANFILE_VERSION.captures(statement) {
+            let range = caps.name("double").or_else(|| caps.name("single"))?.range();
+            let statement_start = line_start + indent.len();
+
+            return Some(statement_start + range.start..statement_start + range.end);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Test finding the version attribute of a recipe
+    fn test_find_conanfile_version() {
+        let text = "from conan import ConanFile\n\nclass MyLibConan(ConanFile):\n    name = \"mylib\"\n    version = '1.2.3'\n";
+        assert_eq!(&text[find_conanfile_version(text).unwrap()], "1.2.3");
+
+        let text = "class MyLibConan(ConanFile):\n    def set_version(self):\n        self.version = \"1.2.3\"\n";
+        assert_eq!(find_conanfile_version(text), None);
+
+        let text = "class MyLibConan(ConanFile):\n    name = \"mylib\"\n\n    def requirements(self):\n        version = \"2.0.0\"\n        self.requires(f\"zlib/{version}\")\n";
+        assert_eq!(find_conanfile_version(text), None);
+
+        let text = "class Helper:\n    version = \"0.1.0\"\n\nclass MyLibConan(ConanFile):\n\t# The version\n\tversion = \"1.2.3\"\n";
+        assert_eq!(&text[find_conanfile_version(text).unwrap()], "1.2.3");
+    }
+}
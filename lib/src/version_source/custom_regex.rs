@@ -1,5 +1,4 @@
 use std::borrow::Cow;
-use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
@@ -7,7 +6,7 @@ use regex::Regex;
 
 use crate::project::VutError;
 use crate::util;
-use crate::version::Version;
+use crate::version::{ParseMode, Version};
 use crate::version_source::VersionSource;
 
 pub struct CustomRegexSourceTemplate {
@@ -46,21 +45,11 @@ impl CustomRegexSourceTemplate {
 
 impl CustomRegexSource {
     fn read_file(&self) -> Result<String, VutError> {
-        let mut file = util::open_file(&self.file_path).map_err(VutError::VersionFileOpen)?;
-
-        let mut text = String::new();
-
-        file.read_to_string(&mut text).map_err(VutError::VersionFileRead)?;
-
-        Ok(text)
+        util::read_version_file(&self.file_path)
     }
 
     fn write_file(&mut self, text: &str) -> Result<(), VutError> {
-        let mut file = util::create_file(&self.file_path).map_err(VutError::VersionFileOpen)?;
-
-        file.write(text.as_bytes()).map_err(VutError::VersionFileWrite)?;
-
-        Ok(())
+        util::write_version_file(&self.file_path, text)
     }
 }
 
@@ -73,7 +62,7 @@ impl VersionSource for CustomRegexSource {
         self.file_path.exists()
     }
 
-    fn get_version(&self) -> Result<Version, VutError> {
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
         let version_str = {
             // Read text from file
             let text = self.read_file()?;
@@ -88,7 +77,7 @@ impl VersionSource for CustomRegexSource {
         };
 
         // Parse version string
-        let version = version_str.parse().map_err(|err| VutError::Other(Cow::Owned(err)))?;
+        let version = Version::parse(&version_str, parse_mode).map_err(VutError::VersionParse)?;
 
         Ok(version)
     }
@@ -0,0 +1,253 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+const GEMSPEC_FILE_EXTENSION: &str = "gemspec";
+
+use regex::Regex;
+use tracing::info;
+
+use crate::project::VutError;
+use crate::scheme::ecosystem;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Matches a file loaded by a gemspec whose name ends with "version",
+/// e.g. `require_relative "lib/foo/version"` or `require "foo/version"`.
+static REGEX_REQUIRE_VERSION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^[ \t]*(?:require_relative|require|load)\b[^\r\n]*?['"](?P<path>[^'"\r\n]*version(?:\.rb)?)['"]"#)
+        .unwrap()
+});
+
+/// Matches the version constant in a version file, e.g. `VERSION = "1.2.3"` or `VERSION = '1.2.3'.freeze`.
+static REGEX_VERSION_CONSTANT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r##"(?m)^[ \t]*VERSION[ \t]*=[ \t]*(?:"(?P<double>[^"#\\\r\n]*)"|'(?P<single>[^'\\\r\n]*)')"##).unwrap()
+});
+
+/// Matches a literal version in a gemspec, e.g. `spec.version = "1.2.3"`.
+static REGEX_GEMSPEC_VERSION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r##"(?m)^[ \t]*\w+\.version[ \t]*=[ \t]*(?:"(?P<double>[^"#\\\r\n]*)"|'(?P<single>[^'\\\r\n]*)')"##)
+        .unwrap()
+});
+
+/// Version source for Ruby gems.
+pub struct GemSource {
+    pub path: PathBuf,
+    /// The file containing the version, which is either the version file loaded by the gemspec,
+    /// or the gemspec itself if it specifies the version literally.
+    pub version_file_path: PathBuf,
+    regex: &'static Regex,
+}
+
+impl GemSource {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let mut gemspec_file_paths: Vec<PathBuf> = std::fs::read_dir(path)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == GEMSPEC_FILE_EXTENSION) && p.is_file())
+            .collect();
+
+        gemspec_file_paths.sort();
+
+        gemspec_file_paths
+            .into_iter()
+            .find_map(|gemspec_file_path| Self::from_gemspec(path, &gemspec_file_path))
+    }
+
+    fn from_gemspec(path: &Path, gemspec_file_path: &Path) -> Option<Self> {
+        let gemspec = util::read_version_file(gemspec_file_path).ok()?;
+
+        let has_version = |file_path: &Path, regex: &Regex| {
+            util::read_version_file(file_path)
+                .ok()
+                .is_some_and(|text| find_version(&text, regex).is_some())
+        };
+
+        // Prefer the version file loaded by the gemspec, followed by a literal version in the gemspec,
+        // and finally the conventional lib/<name>/version.rb location.
+        if let Some(version_file_path) = version_file_candidates(path, &gemspec)
+            .into_iter()
+            .find(|p| has_version(p, &REGEX_VERSION_CONSTANT))
+        {
+            return Some(Self {
+                path: path.to_path_buf(),
+                version_file_path,
+                regex: &REGEX_VERSION_CONSTANT,
+            });
+        }
+
+        if find_version(&gemspec, &REGEX_GEMSPEC_VERSION).is_some() {
+            return Some(Self {
+                path: path.to_path_buf(),
+                version_file_path: gemspec_file_path.to_path_buf(),
+                regex: &REGEX_GEMSPEC_VERSION,
+            });
+        }
+
+        // Gem names use dashes to separate namespaces, e.g. foo-bar is Foo::Bar in lib/foo/bar
+        let name = gemspec_file_path.file_stem()?.to_str()?;
+        let version_file_path = path.join("lib").join(name.replace('-', "/")).join("version.rb");
+
+        has_version(&version_file_path, &REGEX_VERSION_CONSTANT).then(|| Self {
+            path: path.to_path_buf(),
+            version_file_path,
+            regex: &REGEX_VERSION_CONSTANT,
+        })
+    }
+}
+
+impl VersionSource for GemSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.version_file_path.exists()
+    }
+
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        let text = util::read_version_file(&self.version_file_path)?;
+
+        let Some(range) = find_version(&text, self.regex) else {
+            info!("No version number found in '{}'.", self.version_file_path.display());
+            return Err(VutError::VersionNotFound);
+        };
+
+        let version = Version::parse(&gem_to_semver(&text[range]), parse_mode).map_err(VutError::VersionParse)?;
+
+        Ok(version)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let text = util::read_version_file(&self.version_file_path)?;
+
+        let range = find_version(&text, self.regex).ok_or_else(|| {
+            VutError::Other(Cow::Owned(format!(
+                "No version number found in '{}'.",
+                self.version_file_path.display()
+            )))
+        })?;
+
+        let text = format!(
+            "{}{}{}",
+            &text[..range.start],
+            ecosystem::gem_version(version),
+            &text[range.end..]
+        );
+
+        util::write_version_file(&self.version_file_path, &text)?;
+
+        Ok(())
+    }
+}
+
+/// Get the possible paths of version files loaded by a gemspec.
+/// Paths may be relative to the gemspec or to its lib directory, which is usually in the load path.
+fn version_file_candidates(path: &Path, gemspec: &str) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    for caps in REGEX_REQUIRE_VERSION.captures_iter(gemspec) {
+        let required = &caps["path"];
+        let required = if required.ends_with(".rb") {
+            Cow::Borrowed(required)
+        } else {
+            Cow::Owned(format!("{}.rb", required))
+        };
+
+        candidates.push(util::normalize_path(path.join(required.as_ref())));
+        candidates.push(util::normalize_path(path.join("lib").join(required.as_ref())));
+
+        // File.expand_path("../lib/foo/version", __FILE__) is relative to the gemspec file itself
+        if let Some(required) = required.strip_prefix("../") {
+            candidates.push(util::normalize_path(path.join(required)));
+        }
+    }
+
+    candidates
+}
+
+/// Find the range of the version in a file using the specified regex.
+fn find_version(text: &str, regex: &Regex) -> Option<Range<usize>> {
+    let caps = regex.captures(text)?;
+
+    caps.name("double").or_else(|| caps.name("single")).map(|m| m.range())
+}
+
+/// Convert a RubyGems version, where the prerelease is separated with a dot, e.g. `1.2.3.beta.1`,
+/// to SemVer. Versions that already use SemVer separators are returned unchanged.
+fn gem_to_semver(version_str: &str) -> Cow<'_, str> {
+    if version_str.contains(['-', '+']) {
+        return Cow::Borrowed(version_str);
+    }
+
+    let segments: Vec<&str> = version_str.split('.').collect();
+    let numeric_count = segments
+        .iter()
+        .take_while(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()))
+        .count();
+
+    if numeric_count == segments.len() {
+        return Cow::Borrowed(version_str);
+    }
+
+    Cow::Owned(format!(
+        "{}-{}",
+        segments[..numeric_count].join("."),
+        segments[numeric_count..].join(".")
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Test locating the version file loaded by a gemspec
+    fn test_version_file_candidates() {
+        let path = Path::new("/gem");
+
+        assert_eq!(
+            version_file_candidates(
+                path,
+                "require_relative \"lib/foo/version\"\n\nGem::Specification.new do |spec|\n"
+            ),
+            vec![
+                PathBuf::from("/gem/lib/foo/version.rb"),
+                PathBuf::from("/gem/lib/lib/foo/version.rb"),
+            ]
+        );
+        assert_eq!(
+            version_file_candidates(path, "require File.expand_path('../lib/foo/version.rb', __FILE__)\n"),
+            vec![
+                PathBuf::from("/lib/foo/version.rb"),
+                PathBuf::from("/gem/lib/foo/version.rb"),
+                PathBuf::from("/gem/lib/foo/version.rb"),
+            ]
+        );
+        assert!(version_file_candidates(path, "require \"json\"\n").is_empty());
+    }
+
+    #[test]
+    /// Test finding the version constant
+    fn test_find_version() {
+        let text = "module Foo\n  VERSION = \"1.2.3.rc.1\".freeze\nend\n";
+        assert_eq!(
+            &text[find_version(text, &REGEX_VERSION_CONSTANT).unwrap()],
+            "1.2.3.rc.1"
+        );
+
+        let text = "Gem::Specification.new do |s|\n  s.name = 'foo'\n  s.version = '0.1.0'\nend\n";
+        assert_eq!(&text[find_version(text, &REGEX_GEMSPEC_VERSION).unwrap()], "0.1.0");
+    }
+
+    #[test]
+    /// Test converting RubyGems versions to SemVer
+    fn test_gem_to_semver() {
+        assert_eq!(gem_to_semver("1.2.3"), "1.2.3");
+        assert_eq!(gem_to_semver("1.2.3.beta.1"), "1.2.3-beta.1");
+        assert_eq!(gem_to_semver("1.2.3.4.rc1"), "1.2.3.4-rc1");
+        assert_eq!(gem_to_semver("1.2.3-beta.1"), "1.2.3-beta.1");
+    }
+}
@@ -0,0 +1,153 @@
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+const PROPERTIES_FILE_NAME: &str = "gradle.properties";
+const BUILD_FILE_NAMES: &[&str] = &["build.gradle.kts", "build.gradle"];
+const VERSION_PROPERTY: &str = "version";
+
+use regex::Regex;
+use tracing::info;
+
+use crate::project::VutError;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Matches a literal version assignment in a Groovy or Kotlin build script,
+/// e.g. `version = "1.2.3"` or `version '1.2.3'`.
+static REGEX_BUILD_SCRIPT_VERSION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^[ \t]*(?:project\.)?version[ \t]*=?[ \t]*(?:"(?P<double>[^"$\r\n]*)"|'(?P<single>[^'$\r\n]*)')"#)
+        .unwrap()
+});
+
+/// Version source for Gradle projects, where the version is specified
+/// in gradle.properties, the build script, or both.
+pub struct GradleSource {
+    pub path: PathBuf,
+    pub properties_file_path: Option<PathBuf>,
+    pub build_file_path: Option<PathBuf>,
+}
+
+impl GradleSource {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        // Only consider files that actually contain a version, as most modules have a build script,
+        // and gradle.properties is also used for build settings.
+        let properties_file_path = Some(path.join(PROPERTIES_FILE_NAME)).filter(|file_path| {
+            util::read_version_file(file_path)
+                .ok()
+                .is_some_and(|text| util::find_property(&text, VERSION_PROPERTY).is_some())
+        });
+
+        let build_file_path = BUILD_FILE_NAMES.iter().map(|name| path.join(name)).find(|file_path| {
+            util::read_version_file(file_path)
+                .ok()
+                .is_some_and(|text| find_build_script_version(&text).is_some())
+        });
+
+        if properties_file_path.is_none() && build_file_path.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            path: path.to_path_buf(),
+            properties_file_path,
+            build_file_path,
+        })
+    }
+}
+
+impl VersionSource for GradleSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.properties_file_path
+            .iter()
+            .chain(&self.build_file_path)
+            .any(|p| p.exists())
+    }
+
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        // A version assigned in the build script takes precedence over gradle.properties
+        let version_str = if let Some(build_file_path) = &self.build_file_path {
+            let text = util::read_version_file(build_file_path)?;
+
+            find_build_script_version(&text).map(|range| text[range].to_owned())
+        } else if let Some(properties_file_path) = &self.properties_file_path {
+            let text = util::read_version_file(properties_file_path)?;
+
+            util::get_property(&text, VERSION_PROPERTY)
+        } else {
+            None
+        };
+
+        let Some(version_str) = version_str else {
+            info!("No version number found in '{}'.", self.path.display());
+            return Err(VutError::VersionNotFound);
+        };
+
+        let version = Version::parse(version_str.trim(), parse_mode).map_err(VutError::VersionParse)?;
+
+        Ok(version)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let version_str = version.to_string();
+
+        if let Some(properties_file_path) = &self.properties_file_path {
+            let text = util::read_version_file(properties_file_path)?;
+
+            if let Some(text) = util::set_property(&text, VERSION_PROPERTY, &version_str) {
+                util::write_version_file(properties_file_path, &text)?;
+            }
+        }
+
+        if let Some(build_file_path) = &self.build_file_path {
+            let text = util::read_version_file(build_file_path)?;
+
+            if let Some(range) = find_build_script_version(&text) {
+                let text = format!("{}{}{}", &text[..range.start], version_str, &text[range.end..]);
+
+                util::write_version_file(build_file_path, &text)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Find the byte range of the first literal version assigned in a build script.
+/// Versions that are computed or use string interpolation are not considered.
+fn find_build_script_version(text: &str) -> Option<Range<usize>> {
+    let caps = REGEX_BUILD_SCRIPT_VERSION.captures(text)?;
+
+    caps.name("double").or_else(|| caps.name("single")).map(|m| m.range())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Test finding literal versions in Groovy and Kotlin build scripts
+    fn test_find_build_script_version() {
+        let find = |text: &str| find_build_script_version(text).map(|range| text[range].to_owned());
+
+        assert_eq!(
+            find("plugins {\n    id(\"java\")\n}\n\ngroup = \"com.example\"\nversion = \"1.2.3\"\n").as_deref(),
+            Some("1.2.3")
+        );
+        assert_eq!(find("version '1.2.3-SNAPSHOT'\n").as_deref(), Some("1.2.3-SNAPSHOT"));
+        assert_eq!(
+            find("allprojects {\n    project.version = '0.1.0'\n}\n").as_deref(),
+            Some("0.1.0")
+        );
+        assert_eq!(find("version = \"$baseVersion.1\"\n"), None);
+        assert_eq!(
+            find("android {\n    defaultConfig {\n        versionName \"1.0\"\n    }\n}\n"),
+            None
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::path::{Path, PathBuf};
+
+const CHART_FILE_NAME: &str = "Chart.yaml";
+const VERSION_KEY: &str = "version";
+const APP_VERSION_KEY: &str = "appVersion";
+
+/// Template used to render fields that are enabled without specifying a template.
+pub const DEFAULT_HELM_TEMPLATE: &str = "{{FullVersion}}";
+
+use tracing::info;
+
+use crate::project::VutError;
+use crate::template::{self, TemplateInput, processor::VutProcessor};
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Options for the Helm version source.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HelmOptions {
+    /// Template used to render the chart version, or None to leave it unchanged.
+    pub version: Option<String>,
+    /// Template used to render the app version, or None to leave it unchanged.
+    pub app_version: Option<String>,
+}
+
+impl Default for HelmOptions {
+    fn default() -> Self {
+        Self {
+            version: Some(DEFAULT_HELM_TEMPLATE.to_owned()),
+            app_version: None,
+        }
+    }
+}
+
+/// Version source for Helm charts.
+pub struct HelmSource {
+    pub path: PathBuf,
+    pub chart_file_path: PathBuf,
+    pub options: HelmOptions,
+}
+
+impl HelmSource {
+    pub fn from_path(path: &Path, options: &HelmOptions) -> Option<Self> {
+        let chart_file_path = path.join(CHART_FILE_NAME);
+
+        if chart_file_path.exists() {
+            Some(Self {
+                path: path.to_path_buf(),
+                chart_file_path,
+                options: options.clone(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl VersionSource for HelmSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.chart_file_path.exists()
+    }
+
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        let text = util::read_version_file(&self.chart_file_path)?;
+
+        // Read the version from the chart version, unless only the app version is driven by Vut
+        let key = if self.options.version.is_none() && self.options.app_version.is_some() {
+            APP_VERSION_KEY
+        } else {
+            VERSION_KEY
+        };
+
+        let Some(version_str) = util::get_yaml_string(&text, key) else {
+            info!("No {} found in '{}'.", key, self.chart_file_path.display());
+            return Err(VutError::VersionNotFound);
+        };
+
+        let version = Version::parse(&version_str, parse_mode).map_err(VutError::VersionParse)?;
+
+        Ok(version)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let mut text = util::read_version_file(&self.chart_file_path)?;
+
+        let template_input = TemplateInput::from_version(version);
+
+        for (key, template) in [
+            (VERSION_KEY, &self.options.version),
+            (APP_VERSION_KEY, &self.options.app_version),
+        ] {
+            let Some(template) = template else {
+                continue;
+            };
+
+            let value = template::render_template::<VutProcessor>(template, &template_input)
+                .map_err(VutError::TemplateGenerate)?;
+
+            text = util::set_yaml_string(&text, key, &value);
+        }
+
+        util::write_version_file(&self.chart_file_path, &text)?;
+
+        Ok(())
+    }
+
+    fn supports_revision(&self) -> bool {
+        // The chart version must be SemVer, while the app version is free-form
+        self.options.version.is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CHART: &str = "apiVersion: v2\nname: app\nversion: 0.1.0 # chart version\nappVersion: \"1.0.0\"\n";
+
+    /// Update a chart with the specified options, returning the resulting Chart.yaml and the version read back from it.
+    fn update_chart(name: &str, options: HelmOptions, version: &str) -> (String, Version) {
+        let dir = util::TestDir::new(name);
+        dir.write(CHART_FILE_NAME, CHART);
+
+        let mut source = HelmSource::from_path(dir.path(), &options).unwrap();
+        source.set_version(&version.parse().unwrap()).unwrap();
+
+        (
+            dir.read(CHART_FILE_NAME),
+            source.get_version(ParseMode::STRICT).unwrap(),
+        )
+    }
+
+    #[test]
+    /// Test updating both fields, with the default template and a custom template
+    fn test_app_version() {
+        let options = HelmOptions {
+            app_version: Some(DEFAULT_HELM_TEMPLATE.to_owned()),
+            ..Default::default()
+        };
+        let (text, version) = update_chart("helm-enabled", options, "1.2.0-rc.1");
+        assert_eq!(
+            text,
+            CHART
+                .replace("0.1.0", "1.2.0-rc.1")
+                .replace("\"1.0.0\"", "\"1.2.0-rc.1\"")
+        );
+        assert_eq!(version, "1.2.0-rc.1".parse().unwrap());
+
+        let options = HelmOptions {
+            app_version: Some("v{{MajorMinor}}".to_owned()),
+            ..Default::default()
+        };
+        let (text, version) = update_chart("helm-template", options, "1.2.0");
+        assert_eq!(text, CHART.replace("0.1.0", "1.2.0").replace("\"1.0.0\"", "\"v1.2\""));
+        assert_eq!(version, "1.2.0".parse().unwrap());
+    }
+
+    #[test]
+    /// Test that only the app version is updated and read when the chart version is disabled
+    fn test_chart_version_disabled() {
+        let options = HelmOptions {
+            version: None,
+            app_version: Some(DEFAULT_HELM_TEMPLATE.to_owned()),
+        };
+        let (text, version) = update_chart("helm-disabled", options, "2.0.0");
+        assert_eq!(text, CHART.replace("\"1.0.0\"", "\"2.0.0\""));
+        assert_eq!(version, "2.0.0".parse().unwrap());
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+const POM_FILE_NAME: &str = "pom.xml";
+const PROJECT_VERSION: &[&str] = &["project", "version"];
+const PARENT_VERSION: &[&str] = &["project", "parent", "version"];
+
+use tracing::{debug, info};
+
+use crate::project::VutError;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Options for the Maven version source.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MavenOptions {
+    /// Whether to update `<parent><version>` in modules whose parent is also updated.
+    pub update_parent_version: bool,
+}
+
+impl Default for MavenOptions {
+    fn default() -> Self {
+        Self {
+            update_parent_version: true,
+        }
+    }
+}
+
+pub struct MavenSource {
+    pub path: PathBuf,
+    pub pom_file_path: PathBuf,
+    pub options: MavenOptions,
+}
+
+/// Location of the version of a Maven project.
+#[derive(Debug, Eq, PartialEq)]
+enum ProjectVersion {
+    /// The version is the text at the specified range of the POM,
+    /// either in the project's version element or in a property it refers to.
+    Literal(Range<usize>),
+    /// There is no version element, or it refers to a property that is not defined in the POM,
+    /// so the version is inherited from the parent.
+    Inherited,
+    /// No version was found.
+    None,
+}
+
+impl MavenSource {
+    pub fn from_path(path: &Path, options: &MavenOptions) -> Option<Self> {
+        let pom_file_path = path.join(POM_FILE_NAME);
+
+        if pom_file_path.exists() {
+            Some(Self {
+                path: path.to_path_buf(),
+                pom_file_path,
+                options: options.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn read_pom_file(&self) -> Result<String, VutError> {
+        util::read_version_file(&self.pom_file_path)
+    }
+
+    fn write_pom_file(&mut self, xml: &str) -> Result<(), VutError> {
+        util::write_version_file(&self.pom_file_path, xml)
+    }
+}
+
+impl VersionSource for MavenSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.pom_file_path.exists()
+    }
+
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        let xml = self.read_pom_file()?;
+
+        let version_str = match project_version(&xml) {
+            ProjectVersion::Literal(range) => util::unescape_xml(&xml[range]).into_owned(),
+            ProjectVersion::Inherited => {
+                info!(
+                    "The project in '{}' inherits its version from its parent, and cannot be used as a version source.",
+                    self.pom_file_path.display()
+                );
+                return Err(VutError::VersionNotFound);
+            }
+            ProjectVersion::None => {
+                info!("No version number found in '{}'.", self.pom_file_path.display());
+                return Err(VutError::VersionNotFound);
+            }
+        };
+
+        let version = Version::parse(&version_str, parse_mode).map_err(VutError::VersionParse)?;
+
+        Ok(version)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let xml = self.read_pom_file()?;
+
+        match project_version(&xml) {
+            ProjectVersion::Literal(range) => {
+                let xml = util::replace_range(&xml, range, &util::escape_xml(&version.to_string()));
+
+                self.write_pom_file(&xml)?;
+            }
+            // Don't add a version to projects that inherit it.
+            // It will be updated through the parent, if the parent is also updated.
+            ProjectVersion::Inherited => {
+                debug!(
+                    "Skipping inherited project version in '{}'.",
+                    self.pom_file_path.display()
+                );
+            }
+            ProjectVersion::None => {}
+        }
+
+        Ok(())
+    }
+
+    fn update_dependencies(&mut self, updated_paths: &[PathBuf], version: &Version) -> Result<(), VutError> {
+        if !self.options.update_parent_version {
+            return Ok(());
+        }
+
+        let xml = self.read_pom_file()?;
+
+        let Some(parent) = parent_coordinates(&xml) else {
+            return Ok(());
+        };
+
+        // Get the coordinates of all updated Maven projects
+        let mut updated_projects: HashSet<(String, String)> = HashSet::new();
+
+        for path in updated_paths {
+            let pom_file_path = path.join(POM_FILE_NAME);
+
+            if pom_file_path.is_file()
+                && let Some(coordinates) = project_coordinates(&util::read_version_file(&pom_file_path)?)
+            {
+                updated_projects.insert(coordinates);
+            }
+        }
+
+        if !updated_projects.contains(&parent) {
+            return Ok(());
+        }
+
+        // Update the version of the parent, unless it refers to a property
+        let Some(range) = util::find_xml_element(&xml, PARENT_VERSION) else {
+            return Ok(());
+        };
+
+        if xml[range.clone()].contains("${") {
+            debug!(
+                "Not updating parent version in '{}', as it refers to a property.",
+                self.pom_file_path.display()
+            );
+            return Ok(());
+        }
+
+        let new_xml = util::replace_range(&xml, range, &util::escape_xml(&version.to_string()));
+
+        if new_xml != xml {
+            self.write_pom_file(&new_xml)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Locate the version of the project in a POM, following a reference to a property,
+/// such as `<version>${revision}</version>`.
+fn project_version(xml: &str) -> ProjectVersion {
+    let Some(range) = util::find_xml_element(xml, PROJECT_VERSION) else {
+        return if util::find_xml_element(xml, PARENT_VERSION).is_some() {
+            ProjectVersion::Inherited
+        } else {
+            ProjectVersion::None
+        };
+    };
+
+    let property = xml[range.clone()].strip_prefix("${").and_then(|s| s.strip_suffix('}'));
+
+    match property {
+        Some(property) => match util::find_xml_element(xml, &["project", "properties", property]) {
+            Some(range) => ProjectVersion::Literal(range),
+            // The property is most likely defined in the parent
+            None => ProjectVersion::Inherited,
+        },
+        None => ProjectVersion::Literal(range),
+    }
+}
+
+/// Get the group ID and artifact ID of the project in a POM.
+/// The group ID is inherited from the parent if not specified.
+fn project_coordinates(xml: &str) -> Option<(String, String)> {
+    let group_id = util::get_xml_text(xml, &["project", "groupId"])
+        .or_else(|| util::get_xml_text(xml, &["project", "parent", "groupId"]))?;
+    let artifact_id = util::get_xml_text(xml, &["project", "artifactId"])?;
+
+    Some((group_id.into_owned(), artifact_id.into_owned()))
+}
+
+/// Get the group ID and artifact ID of the parent of the project in a POM.
+fn parent_coordinates(xml: &str) -> Option<(String, String)> {
+    let group_id = util::get_xml_text(xml, &["project", "parent", "groupId"])?;
+    let artifact_id = util::get_xml_text(xml, &["project", "parent", "artifactId"])?;
+
+    Some((group_id.into_owned(), artifact_id.into_owned()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CHILD_POM: &str = "<project>\n  <parent>\n    <groupId>com.example</groupId>\n    <artifactId>parent</artifactId>\n    <version>1.0.0</version>\n  </parent>\n  <artifactId>child</artifactId>\n</project>\n";
+
+    #[test]
+    /// Test locating the project version, rather than the parent version
+    fn test_project_version() {
+        let xml = CHILD_POM.replace("</project>", "  <version>1.1.0</version>\n</project>");
+        assert_eq!(
+            project_version(&xml),
+            ProjectVersion::Literal(xml.rfind("1.1.0").unwrap()..xml.rfind("1.1.0").unwrap() + 5)
+        );
+
+        assert_eq!(project_version(CHILD_POM), ProjectVersion::Inherited);
+
+        let xml = "<project>\n  <version>${revision}</version>\n  <properties>\n    <revision>2.0.0-SNAPSHOT</revision>\n  </properties>\n</project>\n";
+        let ProjectVersion::Literal(range) = project_version(xml) else {
+            panic!("expected literal version");
+        };
+        assert_eq!(&xml[range], "2.0.0-SNAPSHOT");
+
+        assert_eq!(project_version("<project>\n</project>\n"), ProjectVersion::None);
+    }
+
+    #[test]
+    /// Test that the group ID is inherited from the parent
+    fn test_project_coordinates() {
+        assert_eq!(
+            project_coordinates(CHILD_POM),
+            Some(("com.example".to_owned(), "child".to_owned()))
+        );
+        assert_eq!(
+            parent_coordinates(CHILD_POM),
+            Some(("com.example".to_owned(), "parent".to_owned()))
+        );
+    }
+}
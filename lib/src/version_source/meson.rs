@@ -0,0 +1,206 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+const MESON_BUILD_FILE_NAME: &str = "meson.build";
+const PROJECT_FUNCTION: &str = "project";
+const VERSION_KEYWORD: &str = "version";
+
+use tracing::info;
+
+use crate::project::VutError;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Version source for Meson projects.
+pub struct MesonSource {
+    pub path: PathBuf,
+    pub build_file_path: PathBuf,
+}
+
+impl MesonSource {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let build_file_path = path.join(MESON_BUILD_FILE_NAME);
+
+        // Only consider projects that declare a version, as most meson.build files
+        // in a source tree belong to subdirectories of a project.
+        let text = util::read_version_file(&build_file_path).ok()?;
+        find_project_version(&text)?;
+
+        Some(Self {
+            path: path.to_path_buf(),
+            build_file_path,
+        })
+    }
+}
+
+impl VersionSource for MesonSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.build_file_path.exists()
+    }
+
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        let text = util::read_version_file(&self.build_file_path)?;
+
+        let Some(range) = find_project_version(&text) else {
+            info!("No project version found in '{}'.", self.build_file_path.display());
+            return Err(VutError::VersionNotFound);
+        };
+
+        let version = Version::parse(&text[range], parse_mode).map_err(VutError::VersionParse)?;
+
+        Ok(version)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let text = util::read_version_file(&self.build_file_path)?;
+
+        let range = find_project_version(&text).ok_or_else(|| {
+            VutError::Other(Cow::Owned(format!(
+                "No project version found in '{}'.",
+                self.build_file_path.display()
+            )))
+        })?;
+
+        let text = util::replace_range(&text, range, &version.to_string());
+
+        util::write_version_file(&self.build_file_path, &text)?;
+
+        Ok(())
+    }
+}
+
+/// Find the range of the literal `version:` keyword argument of the `project()` call,
+/// excluding the quotes. Versions that are not a plain string literal,
+/// such as `files('VERSION')` or a format string, are ignored.
+fn find_project_version(text: &str) -> Option<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+
+    // Nesting depth of parentheses, brackets and braces within the project() call, if inside it
+    let mut project_depth: Option<usize> = None;
+
+    while pos < bytes.len() {
+        let c = bytes[pos];
+
+        match c {
+            b'#' => {
+                pos = bytes[pos..]
+                    .iter()
+                    .position(|&c| c == b'\n')
+                    .map_or(bytes.len(), |i| pos + i);
+            }
+            b'\'' => {
+                pos = skip_string(bytes, pos)?;
+            }
+            b'(' | b'[' | b'{' => {
+                if let Some(depth) = &mut project_depth {
+                    *depth += 1;
+                }
+                pos += 1;
+            }
+            b')' | b']' | b'}' => {
+                match project_depth {
+                    Some(1) => return None,
+                    Some(depth) => project_depth = Some(depth - 1),
+                    None => {}
+                }
+                pos += 1;
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                let start = pos;
+                while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
+                    pos += 1;
+                }
+
+                let identifier = &text[start..pos];
+                let next = skip_whitespace(bytes, pos);
+
+                match project_depth {
+                    None if identifier == PROJECT_FUNCTION && bytes.get(next) == Some(&b'(') => {
+                        project_depth = Some(1);
+                        pos = next + 1;
+                    }
+                    Some(1) if identifier == VERSION_KEYWORD && bytes.get(next) == Some(&b':') => {
+                        let value_start = skip_whitespace(bytes, next + 1);
+
+                        // Only a plain single-line string literal can be updated
+                        if bytes.get(value_start) != Some(&b'\'') || bytes[value_start..].starts_with(b"'''") {
+                            return None;
+                        }
+
+                        let value_end = skip_string(bytes, value_start)?;
+                        let range = value_start + 1..value_end - 1;
+
+                        return (!text[range.clone()].contains('\\')).then_some(range);
+                    }
+                    _ => {}
+                }
+            }
+            _ => pos += 1,
+        }
+    }
+
+    None
+}
+
+/// Skip a string starting at the specified position, returning the position after the closing quote.
+/// Both single-line strings and multiline strings (`'''...'''`) are supported.
+fn skip_string(bytes: &[u8], pos: usize) -> Option<usize> {
+    if bytes[pos..].starts_with(b"'''") {
+        let start = pos + 3;
+
+        return bytes[start..]
+            .windows(3)
+            .position(|w| w == b"'''")
+            .map(|i| start + i + 3);
+    }
+
+    let mut pos = pos + 1;
+
+    loop {
+        match *bytes.get(pos)? {
+            b'\\' => pos += 2,
+            b'\'' => return Some(pos + 1),
+            b'\n' => return None,
+            _ => pos += 1,
+        }
+    }
+}
+
+/// Skip whitespace, including newlines, which are allowed within function arguments.
+fn skip_whitespace(bytes: &[u8], mut pos: usize) -> usize {
+    while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+
+    pos
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MESON_BUILD: &str = "# project('old', version: '0.1.0')\r\nproject(\r\n  'mylib', ['c', 'cpp'], # languages\r\n  license: 'MIT',\r\n  default_options: ['version=0.0.1'],\r\n  version : '1.2.3-beta.1',\r\n)\r\n\r\nlib = library('mylib', version: '2.0.0')\r\n";
+
+    #[test]
+    /// Test finding the project version, ignoring comments and nested arguments
+    fn test_find_project_version() {
+        let range = find_project_version(MESON_BUILD).unwrap();
+        assert_eq!(&MESON_BUILD[range], "1.2.3-beta.1");
+
+        assert_eq!(
+            find_project_version("project('mylib', version: files('VERSION'))\n"),
+            None
+        );
+        assert_eq!(
+            find_project_version("project('mylib')\nlibrary('mylib', version: '1.0.0')\n"),
+            None
+        );
+    }
+}
@@ -1,21 +1,50 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use cmake::CMakeSource;
+use composer::ComposerSource;
+use conan::ConanSource;
+use gem::GemSource;
+use gradle::GradleSource;
+use helm::HelmSource;
+pub use maven::MavenOptions;
+use maven::MavenSource;
+use meson::MesonSource;
+use msbuild::MsBuildSource;
+pub use nuspec::NuspecOptions;
+use nuspec::NuspecSource;
 use pyproject::PyProjectSource;
+use python::PythonSource;
+use setup_cfg::SetupCfgSource;
 use strum::IntoEnumIterator;
 use strum_macros::{AsRefStr, EnumIter, EnumString};
+use vcpkg::VcpkgSource;
 
 use crate::project::VutError;
 use crate::util;
-use crate::version::Version;
+use crate::version::{ParseMode, Version};
 
 mod cargo;
+mod cmake;
+mod composer;
+mod conan;
 mod custom_regex;
+mod gem;
+mod gradle;
+mod helm;
+mod maven;
+mod meson;
+mod msbuild;
 mod npm;
+mod nuspec;
 mod pyproject;
+mod python;
+mod setup_cfg;
+mod vcpkg;
 mod version_file;
 
 pub use cargo::*;
 pub use custom_regex::CustomRegexSourceTemplate;
+pub use helm::{DEFAULT_HELM_TEMPLATE, HelmOptions};
 pub use npm::*;
 pub use version_file::*;
 
@@ -23,8 +52,29 @@ pub use version_file::*;
 pub trait VersionSource {
     fn get_path(&self) -> &Path;
     fn exists(&self) -> bool;
-    fn get_version(&self) -> Result<Version, VutError>;
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError>;
     fn set_version(&mut self, version: &Version) -> Result<(), VutError>;
+
+    /// Whether versions with a revision (fourth numeric component) can be written.
+    /// Sources that require SemVer versions, such as Cargo.toml, return false.
+    fn supports_revision(&self) -> bool {
+        true
+    }
+
+    /// Update references to other version sources that have been updated to the specified version,
+    /// such as dependency version requirements.
+    /// Paths are the normalized paths of all updated version sources.
+    fn update_dependencies(&mut self, _updated_paths: &[PathBuf], _version: &Version) -> Result<(), VutError> {
+        Ok(())
+    }
+}
+
+/// Options for built-in version sources that can be configured.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VersionSourceOptions {
+    pub helm: HelmOptions,
+    pub maven: MavenOptions,
+    pub nuspec: NuspecOptions,
 }
 
 #[derive(AsRefStr, Debug, Clone, EnumIter, EnumString, Eq, Hash, PartialEq)]
@@ -34,22 +84,68 @@ pub enum VersionSourceType {
     Cargo,
     Npm,
     PyProject,
+    SetupCfg,
+    Python,
+    Maven,
+    Gradle,
+    MsBuild,
+    Nuspec,
+    Helm,
+    Composer,
+    Gem,
+    CMake,
+    Meson,
+    Vcpkg,
+    Conan,
 }
 
 impl VersionSourceType {
-    pub fn create_from_path(&self, path: &Path) -> Option<Box<dyn VersionSource>> {
+    /// Whether this type is detected when no types are specified.
+    ///
+    /// Only the original types are detected by default, so that adding a type does not change which files existing
+    /// projects update. The other types have to be specified explicitly.
+    pub fn is_default(&self) -> bool {
+        matches!(self, Self::Vut | Self::Cargo | Self::Npm | Self::PyProject)
+    }
+
+    /// Iterate over the types that are detected when no types are specified.
+    pub fn iter_default() -> impl Iterator<Item = Self> {
+        Self::iter().filter(Self::is_default)
+    }
+
+    pub fn create_from_path(&self, path: &Path, options: &VersionSourceOptions) -> Option<Box<dyn VersionSource>> {
         match self {
             Self::Vut => VersionFileSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
             Self::Cargo => CargoSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
             Self::Npm => NpmSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
             Self::PyProject => PyProjectSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
+            Self::SetupCfg => SetupCfgSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
+            Self::Python => PythonSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
+            Self::Maven => {
+                MavenSource::from_path(path, &options.maven).map(|vs| Box::new(vs) as Box<dyn VersionSource>)
+            }
+            Self::Gradle => GradleSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
+            Self::MsBuild => MsBuildSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
+            Self::Nuspec => {
+                NuspecSource::from_path(path, &options.nuspec).map(|vs| Box::new(vs) as Box<dyn VersionSource>)
+            }
+            Self::Helm => HelmSource::from_path(path, &options.helm).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
+            Self::Composer => ComposerSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
+            Self::Gem => GemSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
+            Self::CMake => CMakeSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
+            Self::Meson => MesonSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
+            Self::Vcpkg => VcpkgSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
+            Self::Conan => ConanSource::from_path(path).map(|vs| Box::new(vs) as Box<dyn VersionSource>),
         }
     }
 }
 
 pub fn first_version_source_from_path(path: &Path) -> Option<(VersionSourceType, Box<dyn VersionSource>)> {
-    for st in VersionSourceType::iter() {
-        if let Some(source) = st.create_from_path(path) {
+    // Without a configuration, all version sources use their default options
+    let options = VersionSourceOptions::default();
+
+    for st in VersionSourceType::iter_default() {
+        if let Some(source) = st.create_from_path(path, &options) {
             return Some((st, source));
         }
     }
@@ -61,15 +157,47 @@ pub fn locate_first_version_source_from(start_path: &Path) -> Option<Box<dyn Ver
     util::find_outwards(start_path, first_version_source_from_path).map(|(_, (_, source))| source)
 }
 
-/// Return all version sources found at the specified path.
-pub fn version_sources_from_path(path: &Path) -> Vec<Box<dyn VersionSource>> {
+/// Return all version sources of the default types found at the specified path.
+pub fn version_sources_from_path(path: &Path, options: &VersionSourceOptions) -> Vec<Box<dyn VersionSource>> {
     let mut sources: Vec<Box<dyn VersionSource>> = Vec::new();
 
-    for st in VersionSourceType::iter() {
-        if let Some(source) = st.create_from_path(path) {
+    for st in VersionSourceType::iter_default() {
+        if let Some(source) = st.create_from_path(path, options) {
             sources.push(source);
         }
     }
 
     sources
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_default_version_sources() {
+        let dir = util::TestDir::new("default-sources");
+        dir.write(
+            "pom.xml",
+            "<project><groupId>a</groupId><artifactId>b</artifactId><version>1.2.3</version></project>\n",
+        );
+
+        // Types added after the original ones are only used when specified
+        assert!(first_version_source_from_path(dir.path()).is_none());
+        assert!(version_sources_from_path(dir.path(), &VersionSourceOptions::default()).is_empty());
+        assert!(
+            VersionSourceType::Maven
+                .create_from_path(dir.path(), &VersionSourceOptions::default())
+                .is_some()
+        );
+
+        dir.write("VERSION", "1.2.3");
+
+        let (source_type, _) = first_version_source_from_path(dir.path()).unwrap();
+        assert_eq!(source_type, VersionSourceType::Vut);
+        assert_eq!(
+            version_sources_from_path(dir.path(), &VersionSourceOptions::default()).len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+const PROPS_FILE_NAME: &str = "Directory.Build.props";
+const PROJECT_FILE_EXTENSIONS: &[&str] = &["csproj", "fsproj", "vbproj"];
+
+use tracing::{debug, info};
+
+use crate::project::VutError;
+use crate::scheme::ecosystem;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Version source for .NET projects, where the version is specified by MSBuild properties
+/// in Directory.Build.props or a project file.
+pub struct MsBuildSource {
+    pub path: PathBuf,
+    pub project_file_path: PathBuf,
+}
+
+impl MsBuildSource {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        // Prefer Directory.Build.props, as it applies to all projects below it
+        let mut candidates = vec![path.join(PROPS_FILE_NAME)];
+
+        let mut project_file_paths: Vec<PathBuf> = std::fs::read_dir(path)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| PROJECT_FILE_EXTENSIONS.contains(&ext))
+            })
+            .collect();
+
+        project_file_paths.sort();
+        candidates.extend(project_file_paths);
+
+        // Only consider files that actually specify a version, as projects commonly get it from elsewhere
+        let project_file_path = candidates.into_iter().filter(|p| p.is_file()).find(|p| {
+            util::read_version_file(p)
+                .ok()
+                .is_some_and(|xml| find_version_string(&xml).is_some())
+        })?;
+
+        Some(Self {
+            path: path.to_path_buf(),
+            project_file_path,
+        })
+    }
+}
+
+impl VersionSource for MsBuildSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.project_file_path.exists()
+    }
+
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        let xml = util::read_version_file(&self.project_file_path)?;
+
+        let Some(version_str) = find_version_string(&xml) else {
+            info!("No version number found in '{}'.", self.project_file_path.display());
+            return Err(VutError::VersionNotFound);
+        };
+
+        let version = Version::parse(&version_str, parse_mode).map_err(VutError::VersionParse)?;
+
+        Ok(version)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let xml = util::read_version_file(&self.project_file_path)?;
+
+        let xml = update_version_properties(&xml, version).map_err(|err| {
+            VutError::Other(Cow::Owned(format!(
+                "Cannot update '{}': {}",
+                self.project_file_path.display(),
+                err
+            )))
+        })?;
+
+        util::write_version_file(&self.project_file_path, &xml)?;
+
+        Ok(())
+    }
+}
+
+/// Find the ranges of the values of all literal properties with the specified name.
+/// Values that refer to other properties, such as `$(VersionPrefix)`, or contain wildcards are excluded.
+fn find_literal_properties(xml: &str, name: &str) -> Vec<Range<usize>> {
+    util::find_xml_elements(xml, &["Project", "PropertyGroup", name])
+        .into_iter()
+        .filter(|range| !xml[range.clone()].contains(['$', '*']))
+        .collect()
+}
+
+/// Get the version specified by the `Version` property, or by `VersionPrefix` and `VersionSuffix`.
+fn find_version_string(xml: &str) -> Option<String> {
+    if let Some(range) = find_literal_properties(xml, "Version").into_iter().next() {
+        return Some(util::unescape_xml(&xml[range]).into_owned());
+    }
+
+    let prefix = find_literal_properties(xml, "VersionPrefix").into_iter().next()?;
+    let suffix = find_literal_properties(xml, "VersionSuffix")
+        .into_iter()
+        .next()
+        .filter(|range| !range.is_empty());
+
+    let mut version_str = util::unescape_xml(&xml[prefix]).into_owned();
+
+    if let Some(suffix) = suffix {
+        version_str.push('-');
+        version_str.push_str(&util::unescape_xml(&xml[suffix]));
+    }
+
+    Some(version_str)
+}
+
+/// Update all literal version properties to the specified version.
+///
+/// `Version` is set to the full version, while the prerelease is split into `VersionSuffix`
+/// when using `VersionPrefix`. `AssemblyVersion` and `FileVersion` must have four numeric components.
+fn update_version_properties(xml: &str, version: &Version) -> Result<String, String> {
+    let mut replacements: Vec<(Range<usize>, String)> = Vec::new();
+
+    for range in find_literal_properties(xml, "Version") {
+        replacements.push((range, util::escape_xml(&version.to_string()).into_owned()));
+    }
+
+    let prefix_ranges = find_literal_properties(xml, "VersionPrefix");
+
+    if !prefix_ranges.is_empty() {
+        if version.build.is_some() {
+            debug!("Build metadata cannot be represented using VersionPrefix and VersionSuffix, and is left out.");
+        }
+
+        let suffix = util::escape_xml(version.prerelease.as_deref().unwrap_or_default()).into_owned();
+
+        for range in &prefix_ranges {
+            replacements.push((range.clone(), ecosystem::numeric_version(version)));
+        }
+
+        let has_suffix = !util::find_xml_elements(xml, &["Project", "PropertyGroup", "VersionSuffix"]).is_empty();
+
+        if has_suffix {
+            for range in find_literal_properties(xml, "VersionSuffix") {
+                replacements.push((range, suffix.clone()));
+            }
+        } else if !suffix.is_empty() {
+            // Add a VersionSuffix after the first VersionPrefix, on its own line with the same indentation
+            let position = insert_after_element(xml, prefix_ranges[0].end);
+            let line_start = xml[..prefix_ranges[0].start].rfind('\n').map_or(0, |n| n + 1);
+            let indent: String = xml[line_start..]
+                .chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect();
+            let newline = if xml.contains("\r\n") { "\r\n" } else { "\n" };
+
+            replacements.push((
+                position..position,
+                format!("{}{}<VersionSuffix>{}</VersionSuffix>", newline, indent, suffix),
+            ));
+        }
+    }
+
+    for name in ["AssemblyVersion", "FileVersion"] {
+        let ranges = find_literal_properties(xml, name);
+
+        if ranges.is_empty() {
+            continue;
+        }
+
+        let c = ecosystem::windows_version_components(version).map_err(|err| format!("{} {}", name, err))?;
+
+        for range in ranges {
+            replacements.push((range, format!("{}.{}.{}.{}", c[0], c[1], c[2], c[3])));
+        }
+    }
+
+    // Replace from the end, so that earlier ranges stay valid
+    replacements.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+
+    let mut xml = xml.to_owned();
+
+    for (range, value) in replacements {
+        xml = util::replace_range(&xml, range, &value);
+    }
+
+    Ok(xml)
+}
+
+/// Find the position after the end tag of the element whose text ends at the specified position.
+fn insert_after_element(xml: &str, text_end: usize) -> usize {
+    xml[text_end..].find('>').map_or(xml.len(), |n| text_end + n + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PROJECT: &str = "<Project Sdk=\"Microsoft.NET.Sdk\">\r\n  <PropertyGroup>\r\n    <VersionPrefix>1.2.0</VersionPrefix>\r\n    <AssemblyVersion>1.0.0.0</AssemblyVersion>\r\n    <FileVersion>$(VersionPrefix).0</FileVersion>\r\n  </PropertyGroup>\r\n</Project>\r\n";
+
+    #[test]
+    /// Test reading the version from Version, or VersionPrefix and VersionSuffix
+    fn test_find_version_string() {
+        assert_eq!(find_version_string(PROJECT).as_deref(), Some("1.2.0"));
+
+        let xml = PROJECT.replace(
+            "</VersionPrefix>",
+            "</VersionPrefix>\r\n    <VersionSuffix>beta.1</VersionSuffix>",
+        );
+        assert_eq!(find_version_string(&xml).as_deref(), Some("1.2.0-beta.1"));
+
+        let xml = PROJECT.replace("VersionPrefix>", "Version>");
+        assert_eq!(find_version_string(&xml).as_deref(), Some("1.2.0"));
+
+        assert_eq!(find_version_string("<Project>\r\n</Project>\r\n"), None);
+    }
+
+    #[test]
+    /// Test updating version properties, adding a VersionSuffix when needed
+    fn test_update_version_properties() {
+        assert_eq!(
+            update_version_properties(PROJECT, &"1.3.0-rc.1".parse().unwrap()).unwrap(),
+            PROJECT
+                .replace(
+                    "<VersionPrefix>1.2.0</VersionPrefix>",
+                    "<VersionPrefix>1.3.0</VersionPrefix>\r\n    <VersionSuffix>rc.1</VersionSuffix>"
+                )
+                .replace("1.0.0.0", "1.3.0.0")
+        );
+
+        assert!(update_version_properties(PROJECT, &"1.70000.0".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    /// Test adding, updating and clearing VersionSuffix, keeping line endings and indentation
+    fn test_update_version_suffix() {
+        let xml = "<Project>\r\n\t<PropertyGroup>\r\n\t\t<VersionPrefix>1.2.0</VersionPrefix>\r\n\t</PropertyGroup>\r\n</Project>\r\n";
+        let with_suffix = xml.replace(
+            "<VersionPrefix>1.2.0</VersionPrefix>",
+            "<VersionPrefix>1.3.0</VersionPrefix>\r\n\t\t<VersionSuffix>beta.1</VersionSuffix>",
+        );
+
+        assert_eq!(
+            update_version_properties(xml, &"1.3.0-beta.1".parse().unwrap()).unwrap(),
+            with_suffix
+        );
+        assert_eq!(
+            update_version_properties(xml, &"1.3.0".parse().unwrap()).unwrap(),
+            xml.replace("1.2.0", "1.3.0")
+        );
+        assert_eq!(
+            update_version_properties(&with_suffix, &"1.3.0-beta.2".parse().unwrap()).unwrap(),
+            with_suffix.replace("beta.1", "beta.2")
+        );
+        assert_eq!(
+            update_version_properties(&with_suffix, &"1.3.0".parse().unwrap()).unwrap(),
+            with_suffix.replace("beta.1", "")
+        );
+    }
+}
@@ -1,12 +1,21 @@
 use std::borrow::Cow;
-use std::io::{Read, Write};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 const PACKAGE_FILE_NAME: &str = "package.json";
+const LOCK_FILE_NAMES: &[&str] = &["package-lock.json", "npm-shrinkwrap.json"];
+const DEPENDENCY_KEYS: &[&str] = &[
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+use tracing::{debug, info};
 
 use crate::project::VutError;
 use crate::util;
-use crate::version::Version;
+use crate::version::{ParseMode, Version};
 use crate::version_source::VersionSource;
 
 pub struct NpmSource {
@@ -29,21 +38,20 @@ impl NpmSource {
     }
 
     fn read_package_file(&self) -> Result<String, VutError> {
-        let mut file = util::open_file(&self.package_file_path).map_err(VutError::VersionFileOpen)?;
-
-        let mut json_str = String::new();
-
-        file.read_to_string(&mut json_str).map_err(VutError::VersionFileRead)?;
-
-        Ok(json_str)
+        util::read_version_file(&self.package_file_path)
     }
 
     fn write_package_file(&mut self, json_str: &str) -> Result<(), VutError> {
-        let mut file = util::create_file(&self.package_file_path).map_err(VutError::VersionFileOpen)?;
-
-        file.write(json_str.as_bytes()).map_err(VutError::VersionFileWrite)?;
+        util::write_version_file(&self.package_file_path, json_str)
+    }
 
-        Ok(())
+    /// Get the paths of any lockfiles belonging to this package.
+    fn lock_file_paths(&self) -> Vec<PathBuf> {
+        LOCK_FILE_NAMES
+            .iter()
+            .map(|name| self.path.join(name))
+            .filter(|path| path.is_file())
+            .collect()
     }
 }
 
@@ -56,7 +64,7 @@ impl VersionSource for NpmSource {
         self.package_file_path.exists()
     }
 
-    fn get_version(&self) -> Result<Version, VutError> {
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
         let version_str = {
             // Read package file to JSON string
             let json_str = self.read_package_file()?;
@@ -74,7 +82,7 @@ impl VersionSource for NpmSource {
         };
 
         // Parse version string
-        let version = version_str.parse().map_err(|err| VutError::Other(Cow::Owned(err)))?;
+        let version = Version::parse(&version_str, parse_mode).map_err(VutError::VersionParse)?;
 
         Ok(version)
     }
@@ -83,20 +91,222 @@ impl VersionSource for NpmSource {
         // Read package file to JSON string
         let json_str = self.read_package_file()?;
 
-        // Deserialize into JSON Value
-        let mut package: serde_json::Value =
-            serde_json::from_str(&json_str).map_err(|err| VutError::Other(Cow::Owned(err.to_string())))?;
+        // Make sure the file is valid JSON before editing it
+        util::parse_json(&json_str)?;
 
-        // Replace version number
-        package["version"] = serde_json::Value::from(version.to_string());
-
-        // Serialize updated document to string
-        let json_str =
-            serde_json::to_string_pretty(&package).map_err(|err| VutError::Other(Cow::Owned(err.to_string())))?;
+        // Replace version number in place, preserving the formatting of the rest of the file.
+        // Packages without a version, such as private workspace roots, are left unchanged.
+        let Some(json_str) = util::set_json_string(&json_str, &["version"], &version.to_string()) else {
+            info!(
+                "Skipping '{}', as it does not have a version.",
+                self.package_file_path.display()
+            );
+            return Ok(());
+        };
 
         // Overwrite package file
         self.write_package_file(&json_str)?;
 
+        // Update the package's own version in any lockfiles
+        for lock_file_path in self.lock_file_paths() {
+            let mut json_str = util::read_version_file(&lock_file_path)?;
+            util::parse_json(&json_str)?;
+
+            for path in [&["version"][..], &["packages", "", "version"][..]] {
+                if let Some(new_json_str) = util::set_json_string(&json_str, path, &version.to_string()) {
+                    json_str = new_json_str;
+                }
+            }
+
+            util::write_version_file(&lock_file_path, &json_str)?;
+        }
+
         Ok(())
     }
+
+    fn supports_revision(&self) -> bool {
+        // npm requires SemVer versions
+        false
+    }
+
+    fn update_dependencies(&mut self, updated_paths: &[PathBuf], version: &Version) -> Result<(), VutError> {
+        // Get the names of all updated npm packages
+        let mut names: HashSet<String> = HashSet::new();
+
+        for path in updated_paths {
+            let package_file_path = path.join(PACKAGE_FILE_NAME);
+
+            if package_file_path.is_file() {
+                let package = util::parse_json(&util::read_version_file(&package_file_path)?)?;
+
+                if let Some(name) = package["name"].as_str() {
+                    names.insert(name.to_owned());
+                }
+            }
+        }
+
+        if names.is_empty() {
+            return Ok(());
+        }
+
+        // Update dependency ranges in the package file
+        let json_str = self.read_package_file()?;
+        let package = util::parse_json(&json_str)?;
+
+        let new_json_str = update_dependency_ranges(&json_str, &[], &package, &names, version);
+        if new_json_str != json_str {
+            self.write_package_file(&new_json_str)?;
+        }
+
+        // Update workspace packages and dependency ranges in any lockfiles
+        for lock_file_path in self.lock_file_paths() {
+            let json_str = util::read_version_file(&lock_file_path)?;
+            let lock = util::parse_json(&json_str)?;
+
+            let mut new_json_str = json_str.clone();
+
+            if let Some(packages) = lock["packages"].as_object() {
+                for (key, package) in packages {
+                    let is_updated_workspace_package = !key.is_empty()
+                        && !key.starts_with("node_modules/")
+                        && updated_paths.contains(&util::normalize_path(self.path.join(key)));
+
+                    if is_updated_workspace_package {
+                        if let Some(updated) =
+                            util::set_json_string(&new_json_str, &["packages", key, "version"], &version.to_string())
+                        {
+                            new_json_str = updated;
+                        }
+                    } else if !key.is_empty() {
+                        continue;
+                    }
+
+                    new_json_str =
+                        update_dependency_ranges(&new_json_str, &["packages", key], package, &names, version);
+                }
+            }
+
+            if new_json_str != json_str {
+                util::write_version_file(&lock_file_path, &new_json_str)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Update the ranges of dependencies on the specified packages in the object at the specified path.
+fn update_dependency_ranges(
+    json_str: &str,
+    path: &[&str],
+    object: &serde_json::Value,
+    names: &HashSet<String>,
+    version: &Version,
+) -> String {
+    let mut json_str = json_str.to_owned();
+
+    for key in DEPENDENCY_KEYS {
+        let Some(dependencies) = object[key].as_object() else {
+            continue;
+        };
+
+        for (name, range) in dependencies {
+            if !names.contains(name) {
+                continue;
+            }
+
+            let Some(new_range) = range.as_str().and_then(|r| update_range(r, version)) else {
+                debug!(
+                    "Not updating range of dependency '{}', as it is not a single version.",
+                    name
+                );
+                continue;
+            };
+
+            let mut dependency_path = path.to_vec();
+            dependency_path.extend([*key, name.as_str()]);
+
+            if let Some(new_json_str) = util::set_json_string(&json_str, &dependency_path, &new_range) {
+                json_str = new_json_str;
+            }
+        }
+    }
+
+    json_str
+}
+
+/// Replace the version in a simple dependency range (e.g. "^1.0.0", "~1.0.0" or "1.0.0"), keeping its operator.
+/// Workspace protocol ranges (e.g. "workspace:^1.0.0") are also supported.
+/// Returns None if the range is not a single version.
+fn update_range(range: &str, version: &Version) -> Option<String> {
+    let (protocol, range) = match range.strip_prefix("workspace:") {
+        Some(range) => ("workspace:", range),
+        None => ("", range),
+    };
+
+    let version_start = range.find(|c| !matches!(c, '^' | '~' | '=')).unwrap_or(range.len());
+    let (operator, old_version) = range.split_at(version_start);
+
+    // Only update ranges that contain a single complete version
+    old_version.parse::<Version>().ok()?;
+
+    // Build metadata is ignored in ranges, so it is left out.
+    let version = Version {
+        build: None,
+        ..version.clone()
+    };
+
+    Some(format!("{}{}{}", protocol, operator, version))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Test that simple dependency ranges are updated while keeping their operator
+    fn test_update_range() {
+        let version: Version = "1.3.0".parse().unwrap();
+
+        assert_eq!(update_range("^1.2.0", &version), Some("^1.3.0".to_owned()));
+        assert_eq!(update_range("~1.2.0", &version), Some("~1.3.0".to_owned()));
+        assert_eq!(update_range("1.2.0", &version), Some("1.3.0".to_owned()));
+        assert_eq!(
+            update_range("workspace:^1.2.0", &version),
+            Some("workspace:^1.3.0".to_owned())
+        );
+        assert_eq!(update_range("workspace:*", &version), None);
+        assert_eq!(update_range(">=1.0.0 <2.0.0", &version), None);
+        assert_eq!(update_range("^1.2", &version), None);
+    }
+
+    #[test]
+    /// Test that a workspace root without a version is skipped, while its dependency ranges are still updated
+    fn test_versionless_workspace_root() {
+        let dir = util::TestDir::new("npm-versionless-root");
+        let member_path = dir.path().join("packages").join("a");
+
+        let root_json = "{\n  \"name\": \"root\",\n  \"private\": true,\n  \"workspaces\": [\"packages/*\"],\n  \"devDependencies\": { \"a\": \"^0.1.0\" }\n}\n";
+        dir.write(PACKAGE_FILE_NAME, root_json);
+        dir.write(
+            member_path.join(PACKAGE_FILE_NAME),
+            "{\n  \"name\": \"a\",\n  \"version\": \"0.1.0\"\n}\n",
+        );
+
+        let version: Version = "0.2.0".parse().unwrap();
+        let mut root = NpmSource::from_path(dir.path()).unwrap();
+        let mut member = NpmSource::from_path(&member_path).unwrap();
+
+        root.set_version(&version).unwrap();
+        member.set_version(&version).unwrap();
+
+        let updated_paths = [dir.path().to_path_buf(), member_path.clone()];
+        root.update_dependencies(&updated_paths, &version).unwrap();
+
+        assert_eq!(dir.read(PACKAGE_FILE_NAME), root_json.replace("^0.1.0", "^0.2.0"));
+        assert_eq!(
+            dir.read(member_path.join(PACKAGE_FILE_NAME)),
+            "{\n  \"name\": \"a\",\n  \"version\": \"0.2.0\"\n}\n"
+        );
+    }
 }
@@ -0,0 +1,129 @@
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+const NUSPEC_FILE_EXTENSION: &str = "nuspec";
+const VERSION_PATH: &[&str] = &["package", "metadata", "version"];
+
+use tracing::{debug, info};
+
+use crate::project::VutError;
+use crate::scheme::ecosystem;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Options for the nuspec version source.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NuspecOptions {
+    /// Whether to write versions that Chocolatey v1 supports, without dots in the prerelease.
+    pub chocolatey: bool,
+}
+
+/// Version source for NuGet and Chocolatey package manifests.
+pub struct NuspecSource {
+    pub path: PathBuf,
+    pub nuspec_file_path: PathBuf,
+    pub options: NuspecOptions,
+}
+
+impl NuspecSource {
+    pub fn from_path(path: &Path, options: &NuspecOptions) -> Option<Self> {
+        let mut nuspec_file_paths: Vec<PathBuf> = std::fs::read_dir(path)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == NUSPEC_FILE_EXTENSION) && p.is_file())
+            .collect();
+
+        nuspec_file_paths.sort();
+
+        // Only consider manifests with a literal version, as the version is often
+        // a replacement token such as $version$, which is substituted when packing.
+        let nuspec_file_path = nuspec_file_paths.into_iter().find(|p| {
+            util::read_version_file(p)
+                .ok()
+                .is_some_and(|xml| find_version(&xml).is_some())
+        })?;
+
+        Some(Self {
+            path: path.to_path_buf(),
+            nuspec_file_path,
+            options: options.clone(),
+        })
+    }
+}
+
+impl VersionSource for NuspecSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.nuspec_file_path.exists()
+    }
+
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        let xml = util::read_version_file(&self.nuspec_file_path)?;
+
+        let Some(range) = find_version(&xml) else {
+            info!("No version number found in '{}'.", self.nuspec_file_path.display());
+            return Err(VutError::VersionNotFound);
+        };
+
+        let version = Version::parse(&util::unescape_xml(&xml[range]), parse_mode).map_err(VutError::VersionParse)?;
+
+        Ok(version)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let xml = util::read_version_file(&self.nuspec_file_path)?;
+
+        let Some(range) = find_version(&xml) else {
+            debug!(
+                "Skipping '{}', as it does not contain a literal version.",
+                self.nuspec_file_path.display()
+            );
+            return Ok(());
+        };
+
+        // NuGet versions cannot contain build metadata, and have a normalized form
+        let version_str = if self.options.chocolatey {
+            ecosystem::chocolatey_version(version)
+        } else {
+            ecosystem::nuget_version(version)
+        };
+
+        if version.build.is_some() {
+            info!(
+                "Build metadata is not supported in '{}', and is left out.",
+                self.nuspec_file_path.display()
+            );
+        }
+
+        let xml = util::replace_range(&xml, range, &util::escape_xml(&version_str));
+
+        util::write_version_file(&self.nuspec_file_path, &xml)?;
+
+        Ok(())
+    }
+}
+
+/// Find the range of the literal version of the package, ignoring replacement tokens such as `$version$`.
+fn find_version(xml: &str) -> Option<Range<usize>> {
+    util::find_xml_element(xml, VERSION_PATH).filter(|range| !xml[range.clone()].contains('$'))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const NUSPEC: &str = "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?>\r\n<package xmlns=\"http://schemas.microsoft.com/packaging/2015/06/nuspec.xsd\">\r\n  <metadata>\r\n    <id>vut</id>\r\n    <version>1.2.0-beta.1</version>\r\n    <dependencies>\r\n      <dependency id=\"a\" version=\"1.0.0\" />\r\n    </dependencies>\r\n  </metadata>\r\n</package>\r\n";
+
+    #[test]
+    /// Test finding the package version, ignoring replacement tokens
+    fn test_find_version() {
+        let range = find_version(NUSPEC).unwrap();
+        assert_eq!(&NUSPEC[range], "1.2.0-beta.1");
+
+        assert_eq!(find_version(&NUSPEC.replace("1.2.0-beta.1", "$version$")), None);
+    }
+}
@@ -1,5 +1,4 @@
 use std::borrow::Cow;
-use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
 const PROJECT_FILE_NAME: &str = "pyproject.toml";
@@ -7,15 +6,32 @@ const PROJECT_FILE_NAME: &str = "pyproject.toml";
 use tracing::info;
 
 use crate::project::VutError;
+use crate::scheme::Pep440Version;
 use crate::util;
-use crate::version::Version;
+use crate::version::{ParseMode, Version};
 use crate::version_source::VersionSource;
+use crate::version_source::python::{self, PythonVersionFile};
 
 pub struct PyProjectSource {
     pub path: PathBuf,
     pub project_file_path: PathBuf,
 }
 
+/// Location of the version of a Python project.
+#[derive(Debug, Eq, PartialEq)]
+enum VersionLocation {
+    /// The version is in [project].
+    Project,
+    /// The version is in [tool.poetry].
+    Poetry,
+    /// The version is dynamic, and read from a file.
+    File(PythonVersionFile),
+    /// The version is dynamic, and determined in an unsupported way (e.g. from version control).
+    Dynamic(String),
+    /// No version was found.
+    None,
+}
+
 impl PyProjectSource {
     pub fn from_path(path: &Path) -> Option<Self> {
         let project_file_path = path.join(PROJECT_FILE_NAME);
@@ -31,21 +47,82 @@ impl PyProjectSource {
     }
 
     fn read_project_file(&self) -> Result<String, VutError> {
-        let mut file = util::open_file(&self.project_file_path).map_err(VutError::VersionFileOpen)?;
-
-        let mut toml_str = String::new();
+        util::read_version_file(&self.project_file_path)
+    }
 
-        file.read_to_string(&mut toml_str).map_err(VutError::VersionFileRead)?;
+    fn write_project_file(&mut self, toml_str: &str) -> Result<(), VutError> {
+        util::write_version_file(&self.project_file_path, toml_str)
+    }
 
-        Ok(toml_str)
+    fn read_project_document(&self) -> Result<toml_edit::DocumentMut, VutError> {
+        self.read_project_file()?
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|err| VutError::Other(Cow::Owned(err.to_string())))
     }
 
-    fn write_project_file(&mut self, toml_str: &str) -> Result<(), VutError> {
-        let mut file = util::create_file(&self.project_file_path).map_err(VutError::VersionFileOpen)?;
+    /// Determine where the version of the project is located, following dynamic version configuration
+    /// of common build backends.
+    fn version_location(&self, doc: &toml_edit::DocumentMut) -> VersionLocation {
+        let get = |path: &[&str]| -> Option<&toml_edit::Item> {
+            path.iter().try_fold(doc.as_item(), |item, key| item.get(key))
+        };
+        let get_str = |path: &[&str]| get(path).and_then(|item| item.as_str());
 
-        file.write(toml_str.as_bytes()).map_err(VutError::VersionFileWrite)?;
+        if get_str(&["project", "version"]).is_some() {
+            return VersionLocation::Project;
+        }
 
-        Ok(())
+        if get_str(&["tool", "poetry", "version"]).is_some() {
+            return VersionLocation::Poetry;
+        }
+
+        let is_dynamic = get(&["project", "dynamic"])
+            .and_then(|d| d.as_array())
+            .is_some_and(|d| d.iter().any(|v| v.as_str() == Some("version")));
+
+        // Hatch: [tool.hatch.version] path = "src/pkg/__about__.py"
+        if let Some(path) = get_str(&["tool", "hatch", "version", "path"])
+            && get_str(&["tool", "hatch", "version", "source"]).is_none_or(|s| s == "regex")
+        {
+            return VersionLocation::File(PythonVersionFile::source_with_default_variables(self.path.join(path)));
+        }
+
+        // PDM: [tool.pdm.version] source = "file", path = "src/pkg/__init__.py"
+        if get_str(&["tool", "pdm", "version", "source"]) == Some("file")
+            && let Some(path) = get_str(&["tool", "pdm", "version", "path"])
+        {
+            return VersionLocation::File(PythonVersionFile::source_with_default_variables(self.path.join(path)));
+        }
+
+        // Setuptools: [tool.setuptools.dynamic] version = { file = "VERSION" } or { attr = "pkg.__version__" }
+        if let Some(version) = get(&["tool", "setuptools", "dynamic", "version"]) {
+            let file = version.get("file").and_then(|f| match f.as_array() {
+                Some(files) => files.get(0).and_then(|f| f.as_str()),
+                None => f.as_str(),
+            });
+
+            if let Some(file) = file {
+                return VersionLocation::File(PythonVersionFile::Text(self.path.join(file)));
+            }
+
+            if let Some(attr) = version.get("attr").and_then(|a| a.as_str())
+                && let Some((module, variable)) = attr.rsplit_once('.')
+                && let Some(path) = python::find_module(&self.path, module)
+            {
+                return VersionLocation::File(PythonVersionFile::Source {
+                    path,
+                    variables: vec![variable.to_owned()],
+                });
+            }
+        }
+
+        if is_dynamic {
+            let backend = get_str(&["build-system", "build-backend"]).unwrap_or("the build backend");
+
+            return VersionLocation::Dynamic(backend.to_owned());
+        }
+
+        VersionLocation::None
     }
 }
 
@@ -58,55 +135,112 @@ impl VersionSource for PyProjectSource {
         self.project_file_path.exists()
     }
 
-    fn get_version(&self) -> Result<Version, VutError> {
+    fn get_version(&self, _parse_mode: ParseMode) -> Result<Version, VutError> {
         let version_str = {
-            // Read TOML from project file
-            let toml_str = self.read_project_file()?;
-
-            // Parse as document
-            let doc = toml_str
-                .parse::<toml_edit::DocumentMut>()
-                .map_err(|err| VutError::Other(Cow::Owned(err.to_string())))?;
+            // Parse project file as document
+            let doc = self.read_project_document()?;
 
             // Get version string
-            match doc["project"]["version"].as_str() {
-                Some(version_str) => version_str.to_owned(),
-                _ => {
+            match self.version_location(&doc) {
+                VersionLocation::Project => doc["project"]["version"].as_str().unwrap().to_owned(),
+                VersionLocation::Poetry => doc["tool"]["poetry"]["version"].as_str().unwrap().to_owned(),
+                VersionLocation::File(file) => file.read_version()?,
+                VersionLocation::Dynamic(backend) => {
+                    return Err(VutError::Other(Cow::Owned(format!(
+                        "The version in '{}' is dynamic and determined by {}, and cannot be used as a version source.",
+                        self.project_file_path.display(),
+                        backend
+                    ))));
+                }
+                VersionLocation::None => {
                     info!("No version number found in '{}'.", self.project_file_path.display());
                     return Err(VutError::VersionNotFound);
                 }
             }
         };
 
-        // Parse version string
-        let version = version_str.parse().map_err(|err| VutError::Other(Cow::Owned(err)))?;
-
-        Ok(version)
+        python::parse_python_version(&version_str)
     }
 
     fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
-        // Read TOML from project file
-        let toml_str = self.read_project_file()?;
+        // Parse project file as document
+        let mut doc = self.read_project_document()?;
 
-        // Parse as document
-        let mut doc = toml_str
-            .parse::<toml_edit::DocumentMut>()
-            .map_err(|err| VutError::Other(Cow::Owned(err.to_string())))?;
+        let version_str = Pep440Version::from_version(version).to_string();
 
-        // If the file does not contain a [project] section, don't try to update version.
-        if !doc.as_table().contains_key("project") {
-            return Ok(());
+        match self.version_location(&doc) {
+            VersionLocation::Project => {
+                util::set_toml_string(&mut doc["project"]["version"], &version_str);
+                self.write_project_file(&doc.to_string())?;
+            }
+            VersionLocation::Poetry => {
+                util::set_toml_string(&mut doc["tool"]["poetry"]["version"], &version_str);
+                self.write_project_file(&doc.to_string())?;
+            }
+            VersionLocation::File(file) => file.write_version(&version_str)?,
+            // Don't try to update versions that Vut cannot manage, or add a version where there is none.
+            VersionLocation::Dynamic(backend) => {
+                info!(
+                    "Skipping '{}', as its version is dynamic and determined by {}.",
+                    self.project_file_path.display(),
+                    backend
+                );
+            }
+            VersionLocation::None => {}
         }
 
-        // Replace version number
-        doc["project"]["version"] = toml_edit::value(version.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
-        // Serialize updated document to string
-        let toml_str = doc.to_string();
+    fn location(toml_str: &str) -> VersionLocation {
+        let source = PyProjectSource {
+            path: PathBuf::from("/project"),
+            project_file_path: PathBuf::from("/project/pyproject.toml"),
+        };
 
-        // Overwrite cargo file
-        self.write_project_file(&toml_str)?;
+        source.version_location(&toml_str.parse().unwrap())
+    }
 
-        Ok(())
+    #[test]
+    /// Test detecting where the version of a project is located
+    fn test_version_location() {
+        assert_eq!(
+            location("[project]\nname = \"a\"\nversion = \"1.0\"\n"),
+            VersionLocation::Project
+        );
+        assert_eq!(
+            location("[tool.poetry]\nname = \"a\"\nversion = \"1.0\"\n"),
+            VersionLocation::Poetry
+        );
+        assert_eq!(
+            location("[project]\ndynamic = [\"version\"]\n\n[tool.hatch.version]\npath = \"src/a/__about__.py\"\n"),
+            VersionLocation::File(PythonVersionFile::source_with_default_variables(PathBuf::from(
+                "/project/src/a/__about__.py"
+            )))
+        );
+        assert_eq!(
+            location("[project]\ndynamic = [\"version\"]\n\n[tool.pdm.version]\nsource = \"file\"\npath = \"a.py\"\n"),
+            VersionLocation::File(PythonVersionFile::source_with_default_variables(PathBuf::from(
+                "/project/a.py"
+            )))
+        );
+        assert_eq!(
+            location(
+                "[project]\ndynamic = [\"version\"]\n\n[tool.setuptools.dynamic]\nversion = { file = \"VERSION\" }\n"
+            ),
+            VersionLocation::File(PythonVersionFile::Text(PathBuf::from("/project/VERSION")))
+        );
+        assert_eq!(
+            location(
+                "[build-system]\nbuild-backend = \"hatchling.build\"\n\n[project]\ndynamic = [\"version\"]\n\n[tool.hatch.version]\nsource = \"vcs\"\n"
+            ),
+            VersionLocation::Dynamic("hatchling.build".to_owned())
+        );
+        assert_eq!(location("[project]\nname = \"a\"\n"), VersionLocation::None);
     }
 }
@@ -0,0 +1,221 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use tracing::info;
+
+use crate::project::VutError;
+use crate::scheme::Pep440Version;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Python source files that commonly contain the version of a package, in order of preference.
+const PYTHON_VERSION_FILE_NAMES: &[&str] = &["__init__.py", "_version.py", "__about__.py"];
+
+/// Variable names that commonly hold the version in Python source files.
+pub const DEFAULT_VERSION_VARIABLES: &[&str] = &["__version__", "VERSION"];
+
+static REGEX_VERSION_ASSIGNMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^(?P<prefix>(?P<name>[A-Za-z_][A-Za-z0-9_]*)\s*(?::\s*[A-Za-z_][\w\[\]\., ]*)?=\s*)(?:"(?P<double>[^"\\\r\n]*)"|'(?P<single>[^'\\\r\n]*)')"#)
+        .unwrap()
+});
+
+/// A file containing the version of a Python package.
+#[derive(Debug, Eq, PartialEq)]
+pub enum PythonVersionFile {
+    /// Python source, where the version is assigned to the first of the specified variables found.
+    Source { path: PathBuf, variables: Vec<String> },
+    /// Plain text file containing only the version.
+    Text(PathBuf),
+}
+
+impl PythonVersionFile {
+    /// Python source where the version is assigned to one of the default variables.
+    pub fn source_with_default_variables(path: PathBuf) -> Self {
+        Self::Source {
+            path,
+            variables: DEFAULT_VERSION_VARIABLES.iter().map(|v| (*v).to_owned()).collect(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Source { path, .. } => path,
+            Self::Text(path) => path,
+        }
+    }
+
+    /// Read the version string from the file.
+    pub fn read_version(&self) -> Result<String, VutError> {
+        let text = util::read_version_file(self.path())?;
+
+        match self {
+            Self::Source { path, variables } => {
+                let variables: Vec<&str> = variables.iter().map(|v| v.as_str()).collect();
+
+                match find_python_version(&text, &variables) {
+                    Some(version_str) => Ok(version_str.to_owned()),
+                    None => {
+                        info!("No version number found in '{}'.", path.display());
+                        Err(VutError::VersionNotFound)
+                    }
+                }
+            }
+            Self::Text(_) => Ok(text.trim().to_owned()),
+        }
+    }
+
+    /// Replace the version string in the file, leaving the rest of the file untouched.
+    pub fn write_version(&self, version_str: &str) -> Result<(), VutError> {
+        let text = util::read_version_file(self.path())?;
+
+        let text = match self {
+            Self::Source { path, variables } => {
+                let variables: Vec<&str> = variables.iter().map(|v| v.as_str()).collect();
+
+                replace_python_version(&text, &variables, version_str).ok_or_else(|| {
+                    VutError::Other(Cow::Owned(format!("No version number found in '{}'.", path.display())))
+                })?
+            }
+            Self::Text(_) => {
+                // Keep any trailing whitespace, such as a final newline
+                let trailing = &text[text.trim_end().len()..];
+
+                format!("{}{}", version_str, trailing)
+            }
+        };
+
+        util::write_version_file(self.path(), &text)
+    }
+}
+
+/// Version source for Python packages that assign `__version__` in a source file,
+/// such as `__init__.py` or `_version.py`.
+pub struct PythonSource {
+    pub path: PathBuf,
+    pub version_file: PythonVersionFile,
+}
+
+impl PythonSource {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        // Only consider files that actually assign a version, as any package has an __init__.py
+        PYTHON_VERSION_FILE_NAMES
+            .iter()
+            .map(|name| path.join(name))
+            .filter(|file_path| file_path.is_file())
+            .find(|file_path| {
+                util::read_version_file(file_path)
+                    .ok()
+                    .is_some_and(|text| find_python_version(&text, &["__version__"]).is_some())
+            })
+            .map(|file_path| Self {
+                path: path.to_path_buf(),
+                version_file: PythonVersionFile::Source {
+                    path: file_path,
+                    variables: vec!["__version__".to_owned()],
+                },
+            })
+    }
+}
+
+impl VersionSource for PythonSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.version_file.path().exists()
+    }
+
+    fn get_version(&self, _parse_mode: ParseMode) -> Result<Version, VutError> {
+        parse_python_version(&self.version_file.read_version()?)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        self.version_file
+            .write_version(&Pep440Version::from_version(version).to_string())
+    }
+}
+
+/// Parse a Python version string.
+/// Python versions follow PEP 440 rather than SemVer, so parse it as such and convert it.
+pub fn parse_python_version(version_str: &str) -> Result<Version, VutError> {
+    version_str
+        .parse::<Pep440Version>()
+        .and_then(|v| v.to_version())
+        .map_err(|err| VutError::Other(Cow::Owned(err)))
+}
+
+/// Find the source file of a Python module, e.g. "pkg.about", in the specified directory or its src directory.
+pub fn find_module(path: &Path, module: &str) -> Option<PathBuf> {
+    let module_path: PathBuf = module.split('.').collect();
+
+    [path.to_path_buf(), path.join("src")]
+        .iter()
+        .flat_map(|dir| {
+            [
+                dir.join(&module_path).join("__init__.py"),
+                dir.join(&module_path).with_extension("py"),
+            ]
+        })
+        .find(|path| path.is_file())
+}
+
+/// Find the version assigned to the first of the specified variables found in Python source,
+/// e.g. `__version__ = "1.2.3"`.
+pub fn find_python_version<'a>(text: &'a str, variables: &[&str]) -> Option<&'a str> {
+    REGEX_VERSION_ASSIGNMENT
+        .captures_iter(text)
+        .find(|caps| variables.contains(&&caps["name"]))
+        .and_then(|caps| version_match(&caps))
+        .map(|m| m.as_str())
+}
+
+/// Replace the version assigned to the first of the specified variables found in Python source,
+/// preserving the quote style and everything else in the source.
+/// Returns None if no version assignment was found.
+pub fn replace_python_version(text: &str, variables: &[&str], version_str: &str) -> Option<String> {
+    let caps = REGEX_VERSION_ASSIGNMENT
+        .captures_iter(text)
+        .find(|caps| variables.contains(&&caps["name"]))?;
+
+    let range = version_match(&caps)?.range();
+
+    Some(format!("{}{}{}", &text[..range.start], version_str, &text[range.end..]))
+}
+
+/// Get the quoted version of a version assignment, without the quotes.
+fn version_match<'a>(caps: &regex::Captures<'a>) -> Option<regex::Match<'a>> {
+    caps.name("double").or_else(|| caps.name("single"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SOURCE: &str =
+        "\"\"\"Package.\"\"\"\n\nVERSION_INFO = (1, 2)\n__version__: str = '1.2.0'\nVERSION = \"0.0.1\"\n";
+
+    #[test]
+    /// Test finding and replacing version assignments
+    fn test_python_version() {
+        assert_eq!(find_python_version(SOURCE, DEFAULT_VERSION_VARIABLES), Some("1.2.0"));
+        assert_eq!(find_python_version(SOURCE, &["VERSION"]), Some("0.0.1"));
+        assert_eq!(find_python_version(SOURCE, &["missing"]), None);
+        assert_eq!(
+            find_python_version("__version__ = \"1.2.3'\n", DEFAULT_VERSION_VARIABLES),
+            None
+        );
+        assert_eq!(
+            find_python_version("__version__ = '1.2.3\"\n", DEFAULT_VERSION_VARIABLES),
+            None
+        );
+
+        assert_eq!(
+            replace_python_version(SOURCE, DEFAULT_VERSION_VARIABLES, "1.3.0rc1").unwrap(),
+            SOURCE.replace("'1.2.0'", "'1.3.0rc1'")
+        );
+    }
+}
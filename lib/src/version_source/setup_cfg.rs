@@ -0,0 +1,178 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILE_NAME: &str = "setup.cfg";
+
+use tracing::info;
+
+use crate::project::VutError;
+use crate::scheme::Pep440Version;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+use crate::version_source::python::{self, PythonVersionFile};
+
+/// Version source for Python packages that declare their version in `[metadata]` of setup.cfg.
+pub struct SetupCfgSource {
+    pub path: PathBuf,
+    pub config_file_path: PathBuf,
+}
+
+/// Location of the version of a setup.cfg.
+#[derive(Debug, Eq, PartialEq)]
+enum VersionLocation {
+    /// The version is the value at the specified range of setup.cfg.
+    Inline(Range<usize>),
+    /// The version is read from another file, using `attr:` or `file:`.
+    File(PythonVersionFile),
+}
+
+impl SetupCfgSource {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let config_file_path = path.join(CONFIG_FILE_NAME);
+
+        // Only consider setup.cfg files that actually declare a version, as they are also used to configure tools
+        let text = util::read_version_file(&config_file_path).ok()?;
+        find_metadata_version(&text)?;
+
+        Some(Self {
+            path: path.to_path_buf(),
+            config_file_path,
+        })
+    }
+
+    /// Determine where the version is located, following `attr:` and `file:` directives.
+    fn version_location(&self, text: &str) -> Result<VersionLocation, VutError> {
+        let Some(range) = find_metadata_version(text) else {
+            info!("No version number found in '{}'.", self.config_file_path.display());
+            return Err(VutError::VersionNotFound);
+        };
+
+        let value = &text[range.clone()];
+
+        if let Some(attr) = value.strip_prefix("attr:") {
+            let attr = attr.trim();
+
+            let path = attr
+                .rsplit_once('.')
+                .and_then(|(module, _)| python::find_module(&self.path, module))
+                .ok_or_else(|| {
+                    VutError::Other(Cow::Owned(format!(
+                        "Cannot find the module containing '{}' referenced in '{}'.",
+                        attr,
+                        self.config_file_path.display()
+                    )))
+                })?;
+
+            let variable = attr.rsplit_once('.').map(|(_, variable)| variable).unwrap_or_default();
+
+            return Ok(VersionLocation::File(PythonVersionFile::Source {
+                path,
+                variables: vec![variable.to_owned()],
+            }));
+        }
+
+        if let Some(files) = value.strip_prefix("file:") {
+            let file = files.split(',').next().unwrap_or_default().trim();
+
+            return Ok(VersionLocation::File(PythonVersionFile::Text(self.path.join(file))));
+        }
+
+        Ok(VersionLocation::Inline(range))
+    }
+}
+
+impl VersionSource for SetupCfgSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.config_file_path.exists()
+    }
+
+    fn get_version(&self, _parse_mode: ParseMode) -> Result<Version, VutError> {
+        let text = util::read_version_file(&self.config_file_path)?;
+
+        let version_str = match self.version_location(&text)? {
+            VersionLocation::Inline(range) => text[range].to_owned(),
+            VersionLocation::File(file) => file.read_version()?,
+        };
+
+        python::parse_python_version(&version_str)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let text = util::read_version_file(&self.config_file_path)?;
+
+        let version_str = Pep440Version::from_version(version).to_string();
+
+        match self.version_location(&text)? {
+            VersionLocation::Inline(range) => {
+                let text = format!("{}{}{}", &text[..range.start], version_str, &text[range.end..]);
+
+                util::write_version_file(&self.config_file_path, &text)?;
+            }
+            VersionLocation::File(file) => file.write_version(&version_str)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Find the byte range of the value of `version` in the `[metadata]` section of a setup.cfg.
+fn find_metadata_version(text: &str) -> Option<Range<usize>> {
+    let mut in_metadata = false;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            in_metadata = trimmed[1..trimmed.len() - 1].trim() == "metadata";
+            continue;
+        }
+
+        // Skip comments, and indented continuation lines of multi-line values
+        if !in_metadata || trimmed.starts_with(['#', ';']) || line.starts_with([' ', '\t']) {
+            continue;
+        }
+
+        let Some(delimiter) = line.find(['=', ':']) else {
+            continue;
+        };
+
+        if line[..delimiter].trim() != "version" {
+            continue;
+        }
+
+        let value = &line[delimiter + 1..];
+        let value_start = line_start + delimiter + 1 + (value.len() - value.trim_start().len());
+        let value_end = value_start + value.trim().len();
+
+        return Some(value_start..value_end);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SETUP_CFG: &str = "[bdist_wheel]\r\nversion = 0.0.0\r\n\r\n[metadata]\r\nname = pkg\r\ndescription =\r\n    version: not this\r\n; version = 0.0.1\r\nversion : 1.2.0rc1 \r\n";
+
+    #[test]
+    /// Test finding the version in the metadata section
+    fn test_find_metadata_version() {
+        let range = find_metadata_version(SETUP_CFG).unwrap();
+        assert_eq!(&SETUP_CFG[range], "1.2.0rc1");
+
+        assert_eq!(find_metadata_version("[metadata]\nname = pkg\n"), None);
+        assert_eq!(find_metadata_version("[options]\nversion = 1.0\n"), None);
+    }
+}
@@ -0,0 +1,169 @@
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE_NAME: &str = "vcpkg.json";
+const PORT_VERSION_KEY: &str = "port-version";
+const RELAXED_VERSION_KEY: &str = "version";
+
+/// Keys that may contain the version, in order of preference.
+/// `version-date` is not included, as it contains a date rather than a version.
+const VERSION_KEYS: &[&str] = &["version", "version-semver", "version-string"];
+
+use tracing::{debug, warn};
+
+use crate::project::VutError;
+use crate::scheme::ecosystem;
+use crate::util;
+use crate::version::{ParseMode, Version};
+use crate::version_source::VersionSource;
+
+/// Version source for vcpkg manifests.
+pub struct VcpkgSource {
+    pub path: PathBuf,
+    pub manifest_file_path: PathBuf,
+}
+
+impl VcpkgSource {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let manifest_file_path = path.join(MANIFEST_FILE_NAME);
+
+        // The version is optional in vcpkg.json, and is usually left out of projects
+        // that only use it to declare dependencies, so only consider manifests that specify one.
+        let json_str = util::read_version_file(&manifest_file_path).ok()?;
+        find_version_key(&util::parse_json(&json_str).ok()?)?;
+
+        Some(Self {
+            path: path.to_path_buf(),
+            manifest_file_path,
+        })
+    }
+}
+
+impl VersionSource for VcpkgSource {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn exists(&self) -> bool {
+        self.manifest_file_path.exists()
+    }
+
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        let manifest = util::parse_json(&util::read_version_file(&self.manifest_file_path)?)?;
+
+        let version_str = find_version_key(&manifest)
+            .and_then(|key| manifest[key].as_str())
+            .ok_or_else(|| VutError::Other("No version property found!".into()))?;
+
+        let version = Version::parse(version_str, parse_mode).map_err(VutError::VersionParse)?;
+
+        Ok(version)
+    }
+
+    fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
+        let json_str = util::read_version_file(&self.manifest_file_path)?;
+        let manifest = util::parse_json(&json_str)?;
+
+        let key = find_version_key(&manifest).ok_or_else(|| VutError::Other("No version property found!".into()))?;
+        let version_str = manifest_version(key, version);
+
+        if key == RELAXED_VERSION_KEY && (version.prerelease.is_some() || version.build.is_some()) {
+            warn!(
+                "The relaxed vcpkg version scheme only allows numbers, so '{}' is written as '{}' in '{}'.",
+                version,
+                version_str,
+                self.manifest_file_path.display()
+            );
+        }
+
+        if manifest[key].as_str() == Some(version_str.as_str()) {
+            return Ok(());
+        }
+
+        debug!("Updating {} in '{}'.", key, self.manifest_file_path.display());
+
+        let json_str = update_manifest(&json_str, &manifest, key, &version_str)
+            .ok_or_else(|| VutError::Other("No version property found!".into()))?;
+
+        util::write_version_file(&self.manifest_file_path, &json_str)?;
+
+        Ok(())
+    }
+
+    fn supports_revision(&self) -> bool {
+        // Only version-semver requires SemVer, as the relaxed scheme allows any number of components
+        util::read_version_file(&self.manifest_file_path)
+            .and_then(|json_str| util::parse_json(&json_str))
+            .map_or(true, |manifest| find_version_key(&manifest) != Some("version-semver"))
+    }
+}
+
+/// Render a version for the specified key.
+/// The relaxed scheme of `version` only allows numbers, so any prerelease or build metadata is left out.
+fn manifest_version(key: &str, version: &Version) -> String {
+    if key == RELAXED_VERSION_KEY {
+        ecosystem::numeric_version(version)
+    } else {
+        version.to_string()
+    }
+}
+
+/// Replace the version in a manifest in place, preserving the formatting of the rest of the file.
+/// The port version is specific to each version, so it starts over when the version changes.
+fn update_manifest(json_str: &str, manifest: &serde_json::Value, key: &str, version_str: &str) -> Option<String> {
+    let mut json_str = util::set_json_string(json_str, &[key], version_str)?;
+
+    if manifest[PORT_VERSION_KEY]
+        .as_u64()
+        .is_some_and(|port_version| port_version != 0)
+    {
+        let range = util::find_json_value(&json_str, &[PORT_VERSION_KEY])?;
+        json_str = util::replace_range(&json_str, range, "0");
+    }
+
+    Some(json_str)
+}
+
+/// Find the key containing the version in a manifest.
+fn find_version_key(manifest: &serde_json::Value) -> Option<&'static str> {
+    VERSION_KEYS.iter().copied().find(|key| manifest[key].is_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MANIFEST: &str = "{\r\n  \"name\": \"mylib\",\r\n  \"version\": \"1.2.3\",\r\n  \"port-version\": 2,\r\n  \"dependencies\": [\"fmt\"]\r\n}\r\n";
+
+    #[test]
+    /// Test that the relaxed scheme only gets the numeric part of the version
+    fn test_manifest_version() {
+        let version: Version = "1.3.0-beta.1+build.5".parse().unwrap();
+
+        assert_eq!(manifest_version("version", &version), "1.3.0");
+        assert_eq!(manifest_version("version-semver", &version), "1.3.0-beta.1+build.5");
+        assert_eq!(manifest_version("version-string", &version), "1.3.0-beta.1+build.5");
+    }
+
+    #[test]
+    /// Test updating the version, resetting the port version
+    fn test_update_manifest() {
+        let manifest = util::parse_json(MANIFEST).unwrap();
+        assert_eq!(find_version_key(&manifest), Some("version"));
+
+        assert_eq!(
+            update_manifest(MANIFEST, &manifest, "version", "1.3.0").unwrap(),
+            MANIFEST.replace("1.2.3", "1.3.0").replace(": 2", ": 0")
+        );
+
+        let json_str = MANIFEST
+            .replace("\"version\"", "\"version-semver\"")
+            .replace(",\r\n  \"port-version\": 2", "");
+        let manifest = util::parse_json(&json_str).unwrap();
+        assert_eq!(find_version_key(&manifest), Some("version-semver"));
+
+        assert_eq!(
+            update_manifest(&json_str, &manifest, "version-semver", "1.3.0-rc.1").unwrap(),
+            json_str.replace("1.2.3", "1.3.0-rc.1")
+        );
+    }
+}
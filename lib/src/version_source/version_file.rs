@@ -1,12 +1,10 @@
-use std::borrow::Cow;
-use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
 const VERSION_FILENAME: &str = "VERSION";
 
 use crate::project::VutError;
 use crate::util;
-use crate::version::Version;
+use crate::version::{ParseMode, Version};
 use crate::version_source::VersionSource;
 
 pub struct VersionFileSource {
@@ -47,29 +45,16 @@ impl VersionSource for VersionFileSource {
         self.version_file_path.exists()
     }
 
-    fn get_version(&self) -> Result<Version, VutError> {
-        let version_str = {
-            let mut file = util::open_file(&self.version_file_path).map_err(VutError::VersionFileOpen)?;
+    fn get_version(&self, parse_mode: ParseMode) -> Result<Version, VutError> {
+        let version_str = util::read_version_file(&self.version_file_path)?;
 
-            let mut version_str = String::new();
-
-            file.read_to_string(&mut version_str)
-                .map_err(VutError::VersionFileRead)?;
-
-            version_str
-        };
-
-        let version = version_str.parse().map_err(|err| VutError::Other(Cow::Owned(err)))?;
+        // Ignore any trailing newline added by text editors
+        let version = Version::parse(version_str.trim_end(), parse_mode).map_err(VutError::VersionParse)?;
 
         Ok(version)
     }
 
     fn set_version(&mut self, version: &Version) -> Result<(), VutError> {
-        let mut file = util::create_file(&self.version_file_path).map_err(VutError::VersionFileOpen)?;
-
-        file.write(version.to_string().as_bytes())
-            .map_err(VutError::VersionFileWrite)?;
-
-        Ok(())
+        util::write_version_file(&self.version_file_path, &version.to_string())
     }
 }